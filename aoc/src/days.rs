@@ -0,0 +1,93 @@
+use anyhow::Result;
+
+/// Parses a day's input and solves both parts.
+pub type Solve = fn(&str) -> Result<[String; 2]>;
+
+/// Every day the runner knows, in order. `aoc new` adds new days at the end.
+pub const DAYS: &[(u32, Solve)] = &[
+    (1, day1::solve),
+    (2, day2::solve),
+    (3, day3::solve),
+    (4, day4::solve),
+    (5, day5::solve),
+    (6, day6::solve),
+    (7, day7::solve),
+];
+
+pub fn find(day: u32) -> Option<Solve> {
+    DAYS.iter()
+        .find(|(number, _)| *number == day)
+        .map(|(_, solve)| *solve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace_root;
+    use anyhow::{bail, Context};
+    use std::fs;
+
+    /// Reads `answers.txt` back into `(part, fixture, answer)` triples.
+    fn expected(answers: &str) -> Result<Vec<(usize, &str, &str)>> {
+        answers
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let mut fields = line.split_whitespace();
+                match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                    (Some(part @ ("1" | "2")), Some(fixture), Some(answer), None) => {
+                        Ok((part.parse()?, fixture, answer))
+                    }
+                    _ => bail!("expected `<part> <fixture> <answer>`, found {line:?}"),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_days_in_order() {
+        assert!(DAYS.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(find(3).is_some());
+        assert!(find(26).is_none());
+    }
+
+    /// Checks every day that has an `answers.txt`, as written by `aoc examples`. Every fixture it
+    /// names must exist.
+    #[test]
+    fn test_examples() -> Result<()> {
+        for (day, solve) in DAYS {
+            let dir = workspace_root().join(format!("day{day}/src"));
+            let Ok(answers) = fs::read_to_string(dir.join("answers.txt")) else {
+                continue;
+            };
+            let answers =
+                expected(&answers).with_context(|| format!("day {day}: invalid answers.txt"))?;
+            for (part, fixture, answer) in answers {
+                let path = dir.join(fixture);
+                let input = fs::read_to_string(&path)
+                    .with_context(|| format!("day {day}: cannot read {}", path.display()))?;
+                let result =
+                    solve(&input).with_context(|| format!("day {day}: cannot solve {fixture}"))?;
+                assert_eq!(
+                    result[part - 1],
+                    answer,
+                    "day {day} part {part} on {fixture}"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expected() -> Result<()> {
+        assert_eq!(
+            expected("1 test.txt 11\n2 test2.txt 31\n")?,
+            vec![(1, "test.txt", "11"), (2, "test2.txt", "31")]
+        );
+        assert!(expected("3 test.txt 11\n").is_err());
+        assert!(expected("1 test.txt\n").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use anyhow::{bail, Context, Result};
+use std::{fs, path::Path};
+
+/// What a saved puzzle description holds for one part: its first example and
+/// the highlighted answer for that example.
+#[derive(Debug, PartialEq)]
+pub struct Part {
+    pub example: Option<String>,
+    pub answer: Option<String>,
+}
+
+/// Extracts the examples and answers from a saved puzzle page and writes them
+/// next to the day's sources: `test.txt` with the example of part one, `test2.txt`
+/// when part two brings its own example, and `answers.txt` with one
+/// `<part> <fixture> <answer>` line per part, which the runner's tests check.
+pub fn save(root: &Path, day: u32, html: &Path) -> Result<()> {
+    let dir = root.join(format!("day{day}/src"));
+    if !dir.is_dir() {
+        bail!(
+            "{} does not exist, create the day with `aoc new {day}`",
+            dir.display()
+        );
+    }
+    let page =
+        fs::read_to_string(html).with_context(|| format!("cannot read {}", html.display()))?;
+
+    let (files, answers) = fixtures(&parts(&page))?;
+    for (name, content) in &files {
+        fs::write(dir.join(name), content)?;
+        println!("Wrote day{day}/src/{name}");
+    }
+    fs::write(dir.join("answers.txt"), &answers)?;
+    print!("Wrote day{day}/src/answers.txt:\n{answers}");
+
+    Ok(())
+}
+
+/// Turns the parts into fixture files and the content of `answers.txt`.
+/// Part two reuses `test.txt` unless its example differs from the one of part one.
+fn fixtures(parts: &[Part]) -> Result<(Vec<(String, String)>, String)> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    let mut answers = String::new();
+    for (index, part) in parts.iter().enumerate() {
+        let Some(answer) = &part.answer else {
+            continue;
+        };
+        let fixture = match &part.example {
+            Some(example) => match files.iter().find(|(_, content)| content == example) {
+                Some((name, _)) => name.clone(),
+                None => {
+                    let name = match files.len() {
+                        0 => "test.txt".to_string(),
+                        n => format!("test{}.txt", n + 1),
+                    };
+                    files.push((name.clone(), example.clone()));
+                    name
+                }
+            },
+            None => match files.first() {
+                Some((name, _)) => name.clone(),
+                None => bail!("no example for part {}", index + 1),
+            },
+        };
+        answers.push_str(&format!("{} {fixture} {answer}\n", index + 1));
+    }
+    if answers.is_empty() {
+        bail!("no highlighted answers found, is this a saved puzzle description?");
+    }
+
+    Ok((files, answers))
+}
+
+/// Splits the page into the descriptions of its parts, falling back to the whole
+/// page when it has no `day-desc` articles.
+pub fn parts(page: &str) -> Vec<Part> {
+    let articles = blocks(page, "<article class=\"day-desc\">", "</article>");
+    let articles = if articles.is_empty() {
+        vec![page]
+    } else {
+        articles
+    };
+
+    articles
+        .into_iter()
+        .map(|article| Part {
+            example: blocks(article, "<pre><code>", "</code></pre>")
+                .first()
+                .map(|block| {
+                    let mut example = text(block);
+                    if !example.ends_with('\n') {
+                        example.push('\n');
+                    }
+                    example
+                }),
+            answer: answer(article),
+        })
+        .collect()
+}
+
+/// The answer is the last highlighted value of the part, written either as
+/// `<code><em>..</em></code>` or `<em><code>..</code></em>`.
+fn answer(article: &str) -> Option<String> {
+    let last = |open: &str, close: &str| {
+        let start = article.rfind(open)? + open.len();
+        let end = start + article[start..].find(close)?;
+        Some((start, &article[start..end]))
+    };
+    let (_, value) = [
+        last("<code><em>", "</em></code>"),
+        last("<em><code>", "</code></em>"),
+    ]
+    .into_iter()
+    .flatten()
+    .max()?;
+
+    Some(text(value).trim().to_string())
+}
+
+/// The contents of every `open ... close` block, in order.
+fn blocks<'a>(mut page: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut result = Vec::new();
+    while let Some(start) = page.find(open) {
+        page = &page[start + open.len()..];
+        let Some(end) = page.find(close) else {
+            break;
+        };
+        result.push(&page[..end]);
+        page = &page[end + close.len()..];
+    }
+
+    result
+}
+
+/// Strips the markup of an HTML fragment and decodes its entities.
+fn text(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        result.push_str(&rest[..start]);
+        rest = match rest[start..].find('>') {
+            Some(end) => &rest[start + end + 1..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+
+    [
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&quot;", "\""),
+        ("&#39;", "'"),
+        ("&apos;", "'"),
+        ("&amp;", "&"),
+    ]
+    .into_iter()
+    .fold(result, |text, (entity, value)| text.replace(entity, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 0: Test ---</h2>
+<p>For example:</p>
+<pre><code>3   4
+4 &lt; 3
+<em>2</em>   5
+</code></pre>
+<p>The distance is <code>1</code>, then <code>2</code>, for a total of <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Using the same example, the score is <em><code>31</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(
+            parts(PAGE),
+            vec![
+                Part {
+                    example: Some("3   4\n4 < 3\n2   5\n".to_string()),
+                    answer: Some("11".to_string()),
+                },
+                Part {
+                    example: None,
+                    answer: Some("31".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fixtures() -> Result<()> {
+        let (files, answers) = fixtures(&parts(PAGE))?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "test.txt");
+        assert_eq!(answers, "1 test.txt 11\n2 test.txt 31\n");
+
+        let page = PAGE.replace("<p>Using", "<pre><code>1\n2\n</code></pre><p>Using");
+        let (files, answers) = fixtures(&parts(&page))?;
+        assert_eq!(files[1], ("test2.txt".to_string(), "1\n2\n".to_string()));
+        assert_eq!(answers, "1 test.txt 11\n2 test2.txt 31\n");
+
+        assert!(fixtures(&parts("<p>nothing here</p>")).is_err());
+
+        Ok(())
+    }
+
+    /// The committed day1 fixtures are what `aoc examples 1 aoc/fixtures/day1.html` writes.
+    #[test]
+    fn test_saved_page() -> Result<()> {
+        let (files, answers) = fixtures(&parts(include_str!("../fixtures/day1.html")))?;
+        assert_eq!(
+            files,
+            vec![(
+                "test.txt".to_string(),
+                include_str!("../../day1/src/test.txt").to_string()
+            )]
+        );
+        assert_eq!(answers, include_str!("../../day1/src/answers.txt"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(text("<em>a</em> &amp;lt; b"), "a &lt; b");
+        assert_eq!(text("x &gt;= y"), "x >= y");
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::{anyhow, bail, Context, Result};
+use days::{Solve, DAYS};
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+mod days;
+mod examples;
+mod scaffold;
+
+fn main() -> Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["new", day] => scaffold::new_day(&workspace_root(), day.parse()?),
+        ["examples", day, html] => examples::save(&workspace_root(), day.parse()?, Path::new(html)),
+        ["run"] => {
+            let failed = DAYS
+                .iter()
+                .filter_map(|(day, solve)| run(*day, *solve).err().map(|e| (day, e)))
+                .collect::<Vec<_>>();
+            if failed.is_empty() {
+                return Ok(());
+            }
+            for (day, e) in &failed {
+                eprintln!("Day {day} failed: {e:#}");
+            }
+
+            bail!("{} of {} days failed", failed.len(), DAYS.len())
+        }
+        ["run", day] => {
+            let day = day.parse()?;
+            let solve = days::find(day).ok_or_else(|| anyhow!("day {day} is not registered"))?;
+
+            run(day, solve)
+        }
+        _ => bail!("usage: aoc new <day> | aoc examples <day> <puzzle.html> | aoc run [day]"),
+    }
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner lives inside the workspace")
+        .to_path_buf()
+}
+
+/// Solves a day with its `input.txt`, read at runtime so days without an input can be skipped.
+fn run(day: u32, solve: Solve) -> Result<()> {
+    let path = workspace_root().join(format!("day{day}/src/input.txt"));
+    let input = fs::read_to_string(&path)
+        .or_else(|e| match e.kind() {
+            ErrorKind::NotFound => Ok(String::new()),
+            _ => Err(e),
+        })
+        .with_context(|| format!("cannot read the input of day {day} from {}", path.display()))?;
+    if input.trim().is_empty() {
+        println!("Day {day}: no input in {}, skipped", path.display());
+        return Ok(());
+    }
+
+    let (took, result) = took::took(|| solve(&input));
+    let [one, two] = result?;
+    println!("Day {day}");
+    println!("Result part one: {one}");
+    println!("Result part two: {two}");
+    println!("Time spent: {took}");
+
+    Ok(())
+}
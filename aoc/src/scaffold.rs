@@ -0,0 +1,120 @@
+use anyhow::{bail, Context, Result};
+use std::{fs, path::Path};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tpl");
+
+/// Creates the `day<day>` crate from the templates and registers it with the runner.
+pub fn new_day(root: &Path, day: u32) -> Result<()> {
+    let dir = root.join(format!("day{day}"));
+    if dir.exists() {
+        bail!("{} already exists", dir.display());
+    }
+
+    // update the runner in memory first, so a failure leaves nothing half done
+    let manifest_path = root.join("aoc/Cargo.toml");
+    let days_path = root.join("aoc/src/days.rs");
+    let manifest = register_dependency(&read(&manifest_path)?, day)?;
+    let days = register_day(&read(&days_path)?, day)?;
+
+    fs::create_dir_all(dir.join("src"))?;
+    for (path, template) in [
+        ("Cargo.toml", CARGO_TOML),
+        ("src/lib.rs", LIB_RS),
+        ("src/main.rs", MAIN_RS),
+        ("src/test.txt", ""),
+        ("src/input.txt", ""),
+    ] {
+        fs::write(dir.join(path), render(template, day))?;
+    }
+    fs::write(manifest_path, manifest)?;
+    fs::write(days_path, days)?;
+
+    println!(
+        "Created day{day}, add the puzzle input to day{day}/src/input.txt and the examples with `aoc examples {day} <puzzle.html>`"
+    );
+
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))
+}
+
+fn render(template: &str, day: u32) -> String {
+    template.replace("{{day}}", &day.to_string())
+}
+
+/// Adds the day crate after the last day in the runner's dependencies.
+fn register_dependency(manifest: &str, day: u32) -> Result<String> {
+    let line = format!("day{day} = {{ path = \"../day{day}\" }}");
+    if manifest
+        .lines()
+        .any(|l| l.starts_with(&format!("day{day} ")))
+    {
+        bail!("day{day} is already a dependency of the runner");
+    }
+    let mut lines = manifest.lines().collect::<Vec<_>>();
+    let Some(last) = lines
+        .iter()
+        .rposition(|l| l.starts_with("day") && l.contains("path = \"../day"))
+    else {
+        bail!("no day dependencies found in the runner's Cargo.toml");
+    };
+
+    lines.insert(last + 1, &line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day to the end of the runner's list of days.
+fn register_day(days: &str, day: u32) -> Result<String> {
+    if days.contains(&format!("day{day}::solve")) {
+        bail!("day{day} is already registered with the runner");
+    }
+    let Some(end) = days.find("\n];") else {
+        bail!("no list of days found in the runner");
+    };
+
+    Ok(format!(
+        "{}\n    ({day}, day{day}::solve),{}",
+        &days[..end],
+        &days[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let main = render(MAIN_RS, 12);
+        assert!(main.contains("use day12::{parse, part_one, part_two};"));
+        for template in [CARGO_TOML, LIB_RS, MAIN_RS] {
+            assert!(!render(template, 8).contains("{{"));
+        }
+        assert!(render(CARGO_TOML, 8).contains("name = \"day8\""));
+    }
+
+    #[test]
+    fn test_register() -> Result<()> {
+        let manifest = "[dependencies]\nanyhow = \"1\"\nday1 = { path = \"../day1\" }\n";
+        assert_eq!(
+            register_dependency(manifest, 2)?,
+            "[dependencies]\nanyhow = \"1\"\nday1 = { path = \"../day1\" }\nday2 = { path = \"../day2\" }\n"
+        );
+        assert!(register_dependency(manifest, 1).is_err());
+        assert!(register_dependency("[dependencies]\n", 1).is_err());
+
+        let days = "pub const DAYS: &[(u32, Solve)] = &[\n    (1, day1::solve),\n];\n";
+        assert_eq!(
+            register_day(days, 2)?,
+            "pub const DAYS: &[(u32, Solve)] = &[\n    (1, day1::solve),\n    (2, day2::solve),\n];\n"
+        );
+        assert!(register_day(days, 1).is_err());
+
+        Ok(())
+    }
+}
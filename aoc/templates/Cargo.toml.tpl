@@ -0,0 +1,12 @@
+[package]
+name = "day{{day}}"
+version = "0.1.0"
+edition = "2024"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+anyhow = "1"
+aoc_lib = { path = "../aoc_lib" }
+nom = "8"
+took = "0.1"
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use aoc_lib::{
+    finish_parse, normalize,
+    parsers::{integer, lines},
+};
+use nom::{IResult, Parser};
+
+pub fn part_one(input: &[u64]) -> u64 {
+    input.iter().sum()
+}
+
+pub fn part_two(input: &[u64]) -> u64 {
+    input.iter().sum()
+}
+
+/// Parses the input and solves both parts, for the `aoc` runner.
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let input = parse(input)?;
+
+    Ok([part_one(&input).to_string(), part_two(&input).to_string()])
+}
+
+pub fn parse(input: &str) -> Result<Vec<u64>> {
+    let input = normalize(input);
+    let result = finish_parse(&input, parse_input(&input))?;
+
+    Ok(result)
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<u64>> {
+    lines(integer).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = include_str!("input.txt");
+    const TESTDATA: &str = include_str!("test.txt");
+
+    #[test]
+    #[ignore = "needs the example and its answer"]
+    fn test_part_one_testdata() -> Result<()> {
+        let input = parse(TESTDATA)?;
+        assert_eq!(part_one(&input), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "needs the puzzle input and its answer"]
+    fn test_part_one() -> Result<()> {
+        let input = parse(DATA)?;
+        assert_eq!(part_one(&input), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "needs the example and its answer"]
+    fn test_part_two_testdata() -> Result<()> {
+        let input = parse(TESTDATA)?;
+        assert_eq!(part_two(&input), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "needs the puzzle input and its answer"]
+    fn test_part_two() -> Result<()> {
+        let input = parse(DATA)?;
+        assert_eq!(part_two(&input), 0);
+
+        Ok(())
+    }
+}
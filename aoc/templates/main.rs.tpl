@@ -0,0 +1,24 @@
+use anyhow::Result;
+use day{{day}}::{parse, part_one, part_two};
+
+const DATA: &str = include_str!("input.txt");
+
+fn main() -> Result<()> {
+    let (took, result) = took::took(|| parse(DATA));
+    println!("Time spent parsing: {took}");
+    let input = result?;
+
+    let (took, result) = took::took(|| part_one(&input));
+    println!("Result part one: {result}");
+    println!("Time spent: {took}");
+
+    let (took, result) = took::took(|| parse(DATA));
+    println!("Time spent parsing: {took}");
+    let input = result?;
+
+    let (took, result) = took::took(|| part_two(&input));
+    println!("Result part two: {result}");
+    println!("Time spent: {took}");
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+/// Counts that fail instead of wrapping around. `bool` only records whether anything was counted.
+pub trait Count: Clone {
+    /// The type name used in overflow errors.
+    const NAME: &str;
+
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn is_zero(&self) -> bool;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+}
+
+impl Count for bool {
+    const NAME: &str = "bool";
+
+    fn zero() -> Self {
+        false
+    }
+
+    fn one() -> Self {
+        true
+    }
+
+    fn is_zero(&self) -> bool {
+        !self
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(*self || *other)
+    }
+}
+
+macro_rules! impl_count {
+    ($($t:ident),*) => {
+        $(
+            impl Count for $t {
+                const NAME: &str = stringify!($t);
+
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_count!(u64, u128);
+
+#[cfg(feature = "bigint")]
+impl Count for num_bigint::BigUint {
+    const NAME: &str = "BigUint";
+
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn one() -> Self {
+        Self::from(1u8)
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        assert_eq!(Count::checked_add(&u64::MAX, &1), None);
+        assert_eq!(Count::checked_add(&2u128, &3), Some(5));
+        assert_eq!(true.checked_add(&false), Some(true));
+        #[cfg(feature = "bigint")]
+        assert_eq!(
+            num_bigint::BigUint::from(u128::MAX).checked_add(&num_bigint::BigUint::one()),
+            Some(num_bigint::BigUint::from(u128::MAX) + 1u8)
+        );
+        assert!(bool::zero().is_zero() && !bool::one().is_zero());
+        assert_eq!(u64::NAME, "u64");
+    }
+}
@@ -0,0 +1,231 @@
+/// Decimal digit manipulation on unsigned integers, without going through strings.
+pub trait Digits: Copy + Sized {
+    /// Number of decimal digits, where zero has one digit.
+    fn digit_count(self) -> u32;
+
+    /// Iterates over the digits, most significant first. Use `.rev()` for least significant first.
+    fn digits(self) -> DigitIter<Self>;
+
+    /// Splits the number into blocks of `width` digits, most significant first. Returns `None`
+    /// if the digit count is not a multiple of `width`.
+    fn blocks(self, width: u32) -> Option<Blocks<Self>>;
+
+    /// Appends the digits of `other`, e.g. `12.concat(345) == Some(12345)`.
+    fn concat(self, other: Self) -> Option<Self>;
+
+    /// Repeats the digits `times` times, e.g. `12.repeat(3) == Some(121212)`. Returns `None` for
+    /// zero repeats.
+    fn repeat(self, times: u32) -> Option<Self>;
+
+    /// Builds a number from digits, most significant first.
+    fn from_digits<I: IntoIterator<Item = u8>>(digits: I) -> Option<Self>;
+}
+
+#[derive(Clone, Debug)]
+pub struct DigitIter<T> {
+    value: T,
+    pow: T,
+    len: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Blocks<T> {
+    value: T,
+    pow: T,
+    width: u32,
+    len: u32,
+}
+
+macro_rules! impl_digits {
+    ($($t:ty),*) => {
+        $(
+            impl Digits for $t {
+                fn digit_count(self) -> u32 {
+                    self.checked_ilog10().unwrap_or(0) + 1
+                }
+
+                fn digits(self) -> DigitIter<Self> {
+                    let len = self.digit_count();
+                    DigitIter {
+                        value: self,
+                        pow: (10 as $t).pow(len - 1),
+                        len,
+                    }
+                }
+
+                fn blocks(self, width: u32) -> Option<Blocks<Self>> {
+                    let count = self.digit_count();
+                    if width == 0 || !count.is_multiple_of(width) {
+                        return None;
+                    }
+                    Some(Blocks {
+                        value: self,
+                        pow: (10 as $t).pow(count - width),
+                        width,
+                        len: count / width,
+                    })
+                }
+
+                fn concat(self, other: Self) -> Option<Self> {
+                    (10 as $t)
+                        .checked_pow(other.digit_count())
+                        .and_then(|pow| self.checked_mul(pow))
+                        .and_then(|value| value.checked_add(other))
+                }
+
+                fn repeat(self, times: u32) -> Option<Self> {
+                    if times == 0 {
+                        return None;
+                    }
+                    (1..times).try_fold(self, |acc, _| acc.concat(self))
+                }
+
+                fn from_digits<I: IntoIterator<Item = u8>>(digits: I) -> Option<Self> {
+                    digits.into_iter().try_fold(0 as $t, |acc, digit| {
+                        acc.checked_mul(10)?.checked_add(digit as $t)
+                    })
+                }
+            }
+
+            impl Iterator for DigitIter<$t> {
+                type Item = u8;
+
+                fn next(&mut self) -> Option<u8> {
+                    if self.len == 0 {
+                        return None;
+                    }
+                    let digit = self.value / self.pow;
+                    self.value %= self.pow;
+                    self.pow /= 10;
+                    self.len -= 1;
+                    Some(digit as u8)
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (self.len as usize, Some(self.len as usize))
+                }
+            }
+
+            impl DoubleEndedIterator for DigitIter<$t> {
+                fn next_back(&mut self) -> Option<u8> {
+                    if self.len == 0 {
+                        return None;
+                    }
+                    let digit = self.value % 10;
+                    self.value /= 10;
+                    self.pow /= 10;
+                    self.len -= 1;
+                    Some(digit as u8)
+                }
+            }
+
+            impl ExactSizeIterator for DigitIter<$t> {}
+
+            impl Iterator for Blocks<$t> {
+                type Item = $t;
+
+                fn next(&mut self) -> Option<$t> {
+                    if self.len == 0 {
+                        return None;
+                    }
+                    let block = self.value / self.pow;
+                    self.value %= self.pow;
+                    self.len -= 1;
+                    if self.len > 0 {
+                        self.pow /= (10 as $t).pow(self.width);
+                    }
+                    Some(block)
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (self.len as usize, Some(self.len as usize))
+                }
+            }
+
+            impl ExactSizeIterator for Blocks<$t> {}
+        )*
+    };
+}
+
+impl_digits!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_count() {
+        assert_eq!(0u32.digit_count(), 1);
+        assert_eq!(9u32.digit_count(), 1);
+        assert_eq!(10u32.digit_count(), 2);
+        assert_eq!(u32::MAX.digit_count(), 10);
+        assert_eq!(u64::MAX.digit_count(), 20);
+        assert_eq!(u128::MAX.digit_count(), 39);
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(12034u64.digits().collect::<Vec<_>>(), vec![1, 2, 0, 3, 4]);
+        assert_eq!(
+            12034u64.digits().rev().collect::<Vec<_>>(),
+            vec![4, 3, 0, 2, 1]
+        );
+        assert_eq!(0u32.digits().collect::<Vec<_>>(), vec![0]);
+
+        let mut iter = 98765u32.digits();
+        assert_eq!(iter.next(), Some(9));
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![8, 7, 6]);
+
+        let max = u128::MAX.digits().collect::<Vec<_>>();
+        assert_eq!(u128::from_digits(max), Some(u128::MAX));
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(
+            123123u64.blocks(3).map(|b| b.collect::<Vec<_>>()),
+            Some(vec![123, 123])
+        );
+        assert_eq!(
+            10010u32.blocks(1).map(|b| b.collect::<Vec<_>>()),
+            Some(vec![1, 0, 0, 1, 0])
+        );
+        assert_eq!(
+            100100u32.blocks(2).map(|b| b.collect::<Vec<_>>()),
+            Some(vec![10, 1, 0])
+        );
+        assert!(12345u32.blocks(2).is_none());
+        assert!(12345u32.blocks(0).is_none());
+        assert_eq!(
+            u64::MAX.blocks(20).map(|b| b.collect::<Vec<_>>()),
+            Some(vec![u64::MAX])
+        );
+    }
+
+    #[test]
+    fn test_concat_and_repeat() {
+        assert_eq!(12u32.concat(345), Some(12345));
+        assert_eq!(12u32.concat(0), Some(120));
+        assert_eq!(0u32.concat(7), Some(7));
+        assert_eq!(u32::MAX.concat(1), None);
+        assert_eq!(12u64.repeat(3), Some(121212));
+        assert_eq!(5u64.repeat(1), Some(5));
+        assert_eq!(5u64.repeat(0), None);
+        assert_eq!(1u64.repeat(20), Some(11111111111111111111));
+        assert_eq!(2u64.repeat(20), None);
+        assert_eq!(
+            123u128.repeat(13),
+            Some(123123123123123123123123123123123123123)
+        );
+    }
+
+    #[test]
+    fn test_from_digits() {
+        assert_eq!(u64::from_digits([1, 2, 0, 3]), Some(1203));
+        assert_eq!(u8::from_digits([2, 5, 5]), Some(255));
+        assert_eq!(u8::from_digits([2, 5, 6]), None);
+        assert_eq!(u32::from_digits([]), Some(0));
+    }
+}
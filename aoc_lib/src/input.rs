@@ -0,0 +1,46 @@
+use std::borrow::Cow;
+
+/// Normalises puzzle input saved by different editors: CRLF line endings become `\n`, trailing
+/// spaces and tabs are stripped from every line, and trailing blank lines are collapsed into a
+/// single final newline.
+///
+/// Leading whitespace is kept, so every character stays in its column. Column-sensitive parsers
+/// only need to treat positions past the end of a line as blank.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let content = input.trim_end_matches(['\n', '\r', ' ', '\t']);
+    let is_clean = !input.contains('\r')
+        && content.lines().all(|line| !line.ends_with([' ', '\t']))
+        && matches!(&input[content.len()..], "" | "\n");
+    if is_clean {
+        return Cow::Borrowed(input);
+    }
+
+    let mut result = String::with_capacity(input.len());
+    for line in content.lines() {
+        result.push_str(line.trim_end_matches([' ', '\t', '\r']));
+        result.push('\n');
+    }
+
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("a\nb\n"), Cow::Borrowed("a\nb\n")));
+        assert!(matches!(normalize("a\n  b"), Cow::Borrowed("a\n  b")));
+        assert_eq!(normalize("a \r\n  b\t\r\n\r\n\r\n"), "a\n  b\n");
+        assert_eq!(normalize("1-2\r\n\r\n3\r\n"), "1-2\n\n3\n");
+        assert_eq!(normalize("a\n\n\n"), "a\n");
+        assert_eq!(normalize("a\nb "), "a\nb\n");
+        assert_eq!(
+            normalize(" 45 64  387 23 \n  6 98  215 314\n"),
+            " 45 64  387 23\n  6 98  215 314\n"
+        );
+        assert_eq!(normalize(""), "");
+        assert_eq!(normalize("\r\n  \r\n"), "");
+    }
+}
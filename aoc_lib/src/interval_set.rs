@@ -0,0 +1,302 @@
+use std::ops::RangeInclusive;
+
+/// Integer types that can be stored in an [`IntervalSet`].
+pub trait Discrete: Copy + Ord {
+    fn successor(self) -> Option<Self>;
+
+    fn predecessor(self) -> Option<Self>;
+
+    /// Number of values in `start..=end`, assuming `start <= end`.
+    fn span(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn span(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers stored as sorted, disjoint and non-adjacent inclusive ranges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RangeInclusive<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Total number of values covered by the set.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|r| T::span(*r.start(), *r.end()))
+            .sum()
+    }
+
+    /// Inserts a range, merging it with any overlapping or adjacent ranges. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = (*range.start(), *range.end());
+        if start > end {
+            return;
+        }
+        let lo = self
+            .ranges
+            .partition_point(|r| r.end().successor().is_some_and(|next| next < start));
+        let hi = self
+            .ranges
+            .partition_point(|r| end.successor().is_none_or(|next| *r.start() <= next));
+        if lo == hi {
+            self.ranges.insert(lo, start..=end);
+            return;
+        }
+        let start = start.min(*self.ranges[lo].start());
+        let end = end.max(*self.ranges[hi - 1].end());
+        self.ranges.splice(lo..hi, [start..=end]);
+    }
+
+    /// Returns the index of the range containing `value`.
+    pub fn position(&self, value: T) -> Option<usize> {
+        let idx = self.ranges.partition_point(|r| *r.end() < value);
+        self.ranges
+            .get(idx)
+            .filter(|r| *r.start() <= value)
+            .map(|_| idx)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.position(value).is_some()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.start() <= y.start() => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, Some(_)) => b.next(),
+                (None, None) => break,
+            };
+            push_merged(&mut ranges, next.unwrap().clone());
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+            if start <= end {
+                push_merged(&mut ranges, start..=end);
+            }
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+        'outer: for range in &self.ranges {
+            let (mut start, end) = (*range.start(), *range.end());
+            while j < other.ranges.len() && *other.ranges[j].end() < start {
+                j += 1;
+            }
+            for cut in other.ranges[j..].iter().take_while(|r| *r.start() <= end) {
+                if *cut.start() > start {
+                    // cut.start() > start guarantees a predecessor exists
+                    ranges.push(start..=cut.start().predecessor().unwrap());
+                }
+                match cut.end().successor() {
+                    Some(next) if next <= end => start = next,
+                    _ => continue 'outer,
+                }
+            }
+            ranges.push(start..=end);
+        }
+
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut input = iter
+            .into_iter()
+            .filter(|r| r.start() <= r.end())
+            .collect::<Vec<_>>();
+        input.sort_by(|a, b| a.start().cmp(b.start()));
+
+        let mut ranges = Vec::with_capacity(input.len());
+        for range in input {
+            push_merged(&mut ranges, range);
+        }
+
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: Discrete> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+/// Appends a range that starts at or after the last one, merging it if they touch.
+fn push_merged<T: Discrete>(ranges: &mut Vec<RangeInclusive<T>>, range: RangeInclusive<T>) {
+    if let Some(last) = ranges.last_mut()
+        && last
+            .end()
+            .successor()
+            .is_none_or(|next| next >= *range.start())
+    {
+        if range.end() > last.end() {
+            *last = *last.start()..=*range.end();
+        }
+        return;
+    }
+    ranges.push(range);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<u64>]) -> IntervalSet<u64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut s = IntervalSet::new();
+        s.insert(10..=14);
+        s.insert(3..=5);
+        s.insert(16..=20);
+        s.insert(12..=18);
+        assert_eq!(s.ranges(), &[3..=5, 10..=20]);
+
+        s.insert(6..=9);
+        assert_eq!(s.ranges(), &[3..=20]);
+
+        s.insert(RangeInclusive::new(30, 29));
+        assert_eq!(s.ranges(), &[3..=20]);
+        assert_eq!(s.len(), 18);
+    }
+
+    #[test]
+    fn test_insert_matches_from_iter() {
+        let input = [
+            5..=9,
+            1..=2,
+            20..=30,
+            3..=3,
+            11..=12,
+            25..=40,
+            10..=10,
+            50..=50,
+        ];
+        let mut inserted = IntervalSet::new();
+        inserted.extend(input.iter().cloned());
+        assert_eq!(inserted, set(&input));
+        assert_eq!(inserted.ranges(), &[1..=3, 5..=12, 20..=40, 50..=50]);
+    }
+
+    #[test]
+    fn test_contains() {
+        let s = set(&[3..=5, 10..=14, 16..=20, 12..=18]);
+        let found = [1, 5, 8, 11, 17, 32]
+            .into_iter()
+            .filter(|id| s.contains(*id))
+            .count();
+        assert_eq!(found, 3);
+        assert_eq!(s.position(17), Some(1));
+        assert_eq!(s.position(9), None);
+        assert!(!IntervalSet::<u64>::new().contains(0));
+    }
+
+    #[test]
+    fn test_extremes() {
+        let s = set(&[
+            0..=0,
+            1..=5,
+            u64::MAX..=u64::MAX,
+            (u64::MAX - 3)..=(u64::MAX - 1),
+        ]);
+        assert_eq!(s.ranges(), &[0..=5, (u64::MAX - 3)..=u64::MAX]);
+        assert!(s.contains(0));
+        assert!(s.contains(u64::MAX));
+        assert_eq!(s.len(), 10);
+
+        let full = set(&[0..=u64::MAX]);
+        assert_eq!(full.len(), 1 << 64);
+        assert_eq!(full.difference(&s), set(&[6..=(u64::MAX - 4)]));
+
+        let signed: IntervalSet<i8> = [i8::MIN..=-1, 0..=i8::MAX].into_iter().collect();
+        assert_eq!(signed.ranges(), &[i8::MIN..=i8::MAX]);
+        assert_eq!(signed.len(), 256);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[1..=10, 20..=30]);
+        let b = set(&[5..=22, 28..=28, 40..=45]);
+
+        assert_eq!(a.union(&b), set(&[1..=30, 40..=45]));
+        assert_eq!(a.intersection(&b), set(&[5..=10, 20..=22, 28..=28]));
+        assert_eq!(a.difference(&b), set(&[1..=4, 23..=27, 29..=30]));
+        assert_eq!(b.difference(&a), set(&[11..=19, 40..=45]));
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(a.intersection(&IntervalSet::new()), IntervalSet::new());
+    }
+}
@@ -1,3 +1,17 @@
+mod count;
+mod digits;
+mod input;
+mod interval_set;
 mod matrix;
+mod parse_error;
+pub mod parsers;
 
+pub use count::Count;
+pub use digits::{Blocks, DigitIter, Digits};
+pub use input::normalize;
+pub use interval_set::{Discrete, IntervalSet};
 pub use matrix::Matrix;
+pub use parse_error::{finish_parse, ParseError};
+
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigUint;
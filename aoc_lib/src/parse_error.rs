@@ -0,0 +1,148 @@
+use nom::{
+    error::{Error, ErrorKind},
+    IResult,
+};
+use std::fmt;
+
+/// A parse failure located in the original input, rendered with the offending line and a caret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    excerpt: String,
+}
+
+impl ParseError {
+    /// Creates an error at byte `offset` of `input`.
+    pub fn new(input: &str, offset: usize, message: impl Into<String>) -> ParseError {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        ParseError {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            message: message.into(),
+            excerpt: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+
+    /// Creates an error at the start of `rest`, which must be a slice of `input`.
+    pub fn at(input: &str, rest: &str, message: impl Into<String>) -> ParseError {
+        let offset = (rest.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+
+        ParseError::new(input, offset, message)
+    }
+
+    pub fn from_nom(input: &str, err: nom::Err<Error<&str>>) -> ParseError {
+        match err {
+            // `all_consuming` reports leftover input as an expected end of file
+            nom::Err::Error(e) | nom::Err::Failure(e) if e.code == ErrorKind::Eof => {
+                ParseError::at(input, e.input, "unexpected trailing input")
+            }
+            // `parsers::char_grid` reports a row that is not as wide as the first one
+            nom::Err::Error(e) | nom::Err::Failure(e) if e.code == ErrorKind::LengthValue => {
+                ParseError::at(input, e.input, "row is not as wide as the first row")
+            }
+            nom::Err::Error(e) | nom::Err::Failure(e) => ParseError::at(
+                input,
+                e.input,
+                format!("unexpected input ({})", e.code.description()),
+            ),
+            nom::Err::Incomplete(_) => ParseError::new(input, input.len(), "incomplete input"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let indent = self
+            .excerpt
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        writeln!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.excerpt)?;
+        write!(f, "{gutter} | {indent}^")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Unwraps the result of parsing the whole of `input`, treating anything but a final line ending
+/// left unconsumed as an error.
+pub fn finish_parse<'a, T>(input: &'a str, result: IResult<&'a str, T>) -> Result<T, ParseError> {
+    let (rest, value) = result.map_err(|err| ParseError::from_nom(input, err))?;
+    match rest {
+        "" | "\n" | "\r\n" => Ok(value),
+        _ => Err(ParseError::at(input, rest, "unexpected trailing input")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::char_grid;
+    use nom::{
+        character::complete::{self, char, line_ending},
+        multi::separated_list1,
+        Parser,
+    };
+
+    fn parse_numbers(input: &str) -> IResult<&str, Vec<u64>> {
+        separated_list1(line_ending, complete::u64).parse(input)
+    }
+
+    #[test]
+    fn test_finish_parse() {
+        assert_eq!(
+            finish_parse("1\n2\n", parse_numbers("1\n2\n")),
+            Ok(vec![1, 2])
+        );
+        assert_eq!(
+            finish_parse("1\r\n2\r\n", parse_numbers("1\r\n2\r\n")),
+            Ok(vec![1, 2])
+        );
+
+        let input = "12\n34\n5x6\n78";
+        let err = finish_parse(input, parse_numbers(input)).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(
+            err.to_string(),
+            "unexpected trailing input at line 3, column 2\n  |\n3 | 5x6\n  |  ^"
+        );
+
+        let input = "12\n\n";
+        let err = finish_parse(input, parse_numbers(input)).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+    }
+
+    #[test]
+    fn test_from_nom() {
+        let input = "1\n2\nx";
+        let err = finish_parse(input, parse_numbers(&input[4..])).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.message, "unexpected input (Digit)");
+
+        let input = "..\n.\n...";
+        let err = finish_parse(input, char_grid(char('.')).parse(input)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.message, "row is not as wide as the first row");
+
+        let err = ParseError::new("ab\ncdéf\ngh", 8, "bad");
+        assert_eq!((err.line, err.column), (2, 5));
+        assert!(err.to_string().ends_with("2 | cdéf\n  |     ^"));
+    }
+}
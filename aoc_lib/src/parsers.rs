@@ -0,0 +1,195 @@
+use nom::{
+    character::complete::{self, char, line_ending, satisfy, space0, space1},
+    combinator::{consumed, map},
+    error::ErrorKind,
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, separated_pair},
+    AsChar, IResult, Parser,
+};
+use std::ops::RangeInclusive;
+
+type Error<'a> = nom::error::Error<&'a str>;
+
+/// Integer types with a nom parser. Signed types accept a leading `-` or `+`.
+pub trait Integer: Sized {
+    fn parse(input: &str) -> IResult<&str, Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ident),*) => {
+        $(
+            impl Integer for $t {
+                fn parse(input: &str) -> IResult<&str, Self> {
+                    complete::$t(input)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+pub fn integer<T: Integer>(input: &str) -> IResult<&str, T> {
+    T::parse(input)
+}
+
+/// An inclusive range written as `start-end`, e.g. `11-22`.
+pub fn range<T: Integer>(input: &str) -> IResult<&str, RangeInclusive<T>> {
+    map(
+        separated_pair(integer, char('-'), integer),
+        |(start, end)| start..=end,
+    )
+    .parse(input)
+}
+
+/// A single decimal digit as its value.
+pub fn digit(input: &str) -> IResult<&str, u8> {
+    map(satisfy(|c| c.is_dec_digit()), |c| c as u8 - b'0').parse(input)
+}
+
+/// One or more items, one per line.
+pub fn lines<'a, P>(parser: P) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    separated_list1(line_ending, parser)
+}
+
+/// One or more items separated by commas.
+pub fn comma_list<'a, P>(
+    parser: P,
+) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    separated_list1(char(','), parser)
+}
+
+/// The empty line between two sections, including the line ending before it.
+pub fn blank_line(input: &str) -> IResult<&str, ()> {
+    map(pair(line_ending, line_ending), |_| ()).parse(input)
+}
+
+/// One or more sections separated by blank lines.
+pub fn sections<'a, P>(
+    parser: P,
+) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    separated_list1(blank_line, parser)
+}
+
+/// A line of cells separated by any number of spaces, ignoring leading and trailing spaces.
+pub fn row<'a, P>(parser: P) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    delimited(space0, separated_list1(space1, parser), space0)
+}
+
+/// Lines of space-aligned cells, as in a table.
+pub fn table<'a, P>(
+    parser: P,
+) -> impl Parser<&'a str, Output = Vec<Vec<P::Output>>, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    lines(row(parser))
+}
+
+/// Lines of single-character cells, all as wide as the first line. A shorter or longer line
+/// fails with [`ErrorKind::LengthValue`] where it stops matching the width of the first one.
+pub fn char_grid<'a, P>(
+    parser: P,
+) -> impl Parser<&'a str, Output = Vec<Vec<P::Output>>, Error = Error<'a>>
+where
+    P: Parser<&'a str, Error = Error<'a>>,
+{
+    let mut rows = lines(consumed(many1(parser)));
+
+    move |input: &'a str| {
+        let (rest, rows) = rows.parse(input)?;
+        let width = rows.first().map_or(0, |(_, cells)| cells.len());
+        if let Some((line, _)) = rows.iter().find(|(_, cells)| cells.len() != width) {
+            let start = line.as_ptr() as usize - input.as_ptr() as usize;
+            let offset = line
+                .char_indices()
+                .nth(width)
+                .map_or(line.len(), |(i, _)| i);
+            return Err(nom::Err::Failure(Error::new(
+                &input[start + offset..],
+                ErrorKind::LengthValue,
+            )));
+        }
+
+        Ok((rest, rows.into_iter().map(|(_, cells)| cells).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::one_of;
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(integer::<u64>("123,"), Ok((",", 123)));
+        assert_eq!(integer::<i32>("-42\n"), Ok(("\n", -42)));
+        assert!(integer::<u8>("256").is_err());
+        assert_eq!(range::<u64>("11-22"), Ok(("", 11..=22)));
+        assert_eq!(range::<i64>("-5--3"), Ok(("", -5..=-3)));
+        assert_eq!(digit("7x"), Ok(("x", 7)));
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(
+            comma_list(range::<u32>).parse("1-2,30-40\n"),
+            Ok(("\n", vec![1..=2, 30..=40]))
+        );
+        assert_eq!(
+            lines(integer::<u32>).parse("1\n2\r\n3\n"),
+            Ok(("\n", vec![1, 2, 3]))
+        );
+        assert_eq!(
+            sections(lines(integer::<u32>)).parse("1\n2\n\n3\n\n4"),
+            Ok(("", vec![vec![1, 2], vec![3], vec![4]]))
+        );
+        assert_eq!(
+            separated_pair(lines(range::<u32>), blank_line, lines(integer::<u32>))
+                .parse("3-5\n10-14\n\n1\n5"),
+            Ok(("", (vec![3..=5, 10..=14], vec![1, 5])))
+        );
+    }
+
+    #[test]
+    fn test_table_and_grid() {
+        assert_eq!(
+            table(integer::<u32>).parse("123 328  51 64 \n 45 64  387 23\n*"),
+            Ok(("\n*", vec![vec![123, 328, 51, 64], vec![45, 64, 387, 23]]))
+        );
+        assert_eq!(
+            char_grid(one_of(".@")).parse("..@\n@.@\n"),
+            Ok(("\n", vec![vec!['.', '.', '@'], vec!['@', '.', '@']]))
+        );
+        assert_eq!(
+            char_grid(digit).parse("12\n34"),
+            Ok(("", vec![vec![1, 2], vec![3, 4]]))
+        );
+        assert_eq!(
+            char_grid(digit).parse("123\n4\n567"),
+            Err(nom::Err::Failure(Error::new(
+                "\n567",
+                ErrorKind::LengthValue
+            )))
+        );
+        assert_eq!(
+            char_grid(digit).parse("12\n345\n67"),
+            Err(nom::Err::Failure(Error::new(
+                "5\n67",
+                ErrorKind::LengthValue
+            )))
+        );
+    }
+}
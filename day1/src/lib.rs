@@ -0,0 +1,277 @@
+use anyhow::Result;
+use aoc_lib::{normalize, ParseError};
+use nom::{
+    branch::alt,
+    character::complete::{self, char},
+    combinator::{all_consuming, map, map_res, value},
+    sequence::pair,
+    IResult, Parser,
+};
+
+pub fn part_one(input: &[isize]) -> usize {
+    Dial::default()
+        .trace(input)
+        .filter(|step| step.ends_at_zero)
+        .count()
+}
+
+pub fn part_two(input: &[isize]) -> usize {
+    Dial::default()
+        .trace(input)
+        .map(|step| step.zero_clicks)
+        .sum()
+}
+
+/// Parses the input and solves both parts, for the `aoc` runner.
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let input = parse(input)?;
+
+    Ok([part_one(&input).to_string(), part_two(&input).to_string()])
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Dial {
+    size: isize,
+    position: isize,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Dial::new(100, 50)
+    }
+}
+
+impl Dial {
+    pub fn new(size: isize, start: isize) -> Dial {
+        assert!(size > 0, "dial size must be positive");
+        Dial {
+            size,
+            position: start.rem_euclid(size),
+        }
+    }
+
+    pub fn rotate(&mut self, rotation: isize) -> Step {
+        let distance = rotation.unsigned_abs();
+        let size = self.size.unsigned_abs();
+        let position = self.position.unsigned_abs();
+        // clicks until the dial first points at zero in the direction of the rotation
+        let to_zero = if rotation >= 0 {
+            size - position
+        } else if position == 0 {
+            size
+        } else {
+            position
+        };
+        let zero_clicks = if distance >= to_zero {
+            1 + (distance - to_zero) / size
+        } else {
+            0
+        };
+
+        self.position = (self.position + rotation % self.size).rem_euclid(self.size);
+
+        Step {
+            position: self.position,
+            zero_clicks,
+            ends_at_zero: self.position == 0,
+        }
+    }
+
+    pub fn trace(mut self, rotations: &[isize]) -> impl Iterator<Item = Step> {
+        rotations.iter().map(move |rotation| self.rotate(*rotation))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub position: isize,
+    /// Number of clicks during the rotation, including the last one, that left the dial at zero.
+    pub zero_clicks: usize,
+    pub ends_at_zero: bool,
+}
+
+pub fn parse(input: &str) -> Result<Vec<isize>> {
+    let input = normalize(input);
+    input
+        .lines()
+        .map(|line| {
+            let (_, rotation) = all_consuming(parse_rotation)
+                .parse(line)
+                .map_err(|err| ParseError::from_nom(&input, err))?;
+
+            Ok(rotation)
+        })
+        .collect()
+}
+
+fn parse_rotation(input: &str) -> IResult<&str, isize> {
+    map(
+        pair(parse_direction, map_res(complete::u64, isize::try_from)),
+        |(sign, clicks)| sign * clicks,
+    )
+    .parse(input)
+}
+
+fn parse_direction(input: &str) -> IResult<&str, isize> {
+    alt((value(-1, char('L')), value(1, char('R')))).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const DATA: &str = include_str!("input.txt");
+    const TESTDATA: &str = include_str!("test.txt");
+
+    #[test]
+    fn test_part_one_testdata() -> Result<()> {
+        assert_eq!(part_one(&parse(TESTDATA)?), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        let input = parse(DATA)?;
+        assert_eq!(part_one(&input), 1123);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two_testdata() -> Result<()> {
+        let input = parse(TESTDATA)?;
+        assert_eq!(part_two(&input), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        let input = parse(DATA)?;
+        assert_eq!(part_two(&input), 6695);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf_testdata() -> Result<()> {
+        let input = TESTDATA.replace('\n', "  \r\n") + "\r\n";
+        assert_eq!(parse(&input)?, parse(TESTDATA)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_testdata() -> Result<()> {
+        let steps = Dial::default().trace(&parse(TESTDATA)?).collect::<Vec<_>>();
+        let positions = steps.iter().map(|step| step.position).collect::<Vec<_>>();
+        assert_eq!(positions, vec![82, 52, 0, 95, 55, 0, 99, 0, 14, 32]);
+        let zero_clicks = steps
+            .iter()
+            .map(|step| step.zero_clicks)
+            .collect::<Vec<_>>();
+        assert_eq!(zero_clicks, vec![1, 0, 1, 0, 1, 1, 0, 1, 0, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() -> Result<()> {
+        assert_eq!(parse("L68\nR48\n")?, vec![-68, 48]);
+
+        for (input, line, column) in [
+            ("L68\n\nR48", 2, 1),
+            ("L68\nl30", 2, 1),
+            ("X5", 1, 1),
+            ("R", 1, 2),
+            ("R-5", 1, 2),
+            ("L1\nR2\néR3", 3, 1),
+            ("L 5", 1, 2),
+        ] {
+            let err = parse(input).unwrap_err().downcast::<ParseError>()?;
+            assert_eq!((err.line, err.column), (line, column), "{input:?}");
+        }
+
+        let err = parse("L68\nR30\nR4x8").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unexpected trailing input at line 3, column 3\n  |\n3 | R4x8\n  |   ^"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut dial = Dial::new(10, -3);
+        assert_eq!(dial.position, 7);
+        assert_eq!(
+            dial.rotate(25),
+            Step {
+                position: 2,
+                zero_clicks: 3,
+                ends_at_zero: false
+            }
+        );
+        assert_eq!(dial.rotate(-2).zero_clicks, 1);
+        assert_eq!(dial.rotate(-20).zero_clicks, 2);
+        assert_eq!(dial.rotate(0).zero_clicks, 0);
+        assert!(dial.rotate(0).ends_at_zero);
+    }
+
+    #[test]
+    fn test_rotate_matches_simulation() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..300 {
+            let size = rng.random_range(1..=200i64) as isize;
+            let start = rng.random_range(-1_000..=1_000i64) as isize;
+            let rotations = (0..20)
+                .map(|_| match rng.random_range(0..4) {
+                    0 => 0,
+                    1 => rng.random_range(-size as i64..=size as i64) as isize,
+                    _ => rng.random_range(-5_000..=5_000i64) as isize,
+                })
+                .collect::<Vec<_>>();
+
+            let steps = Dial::new(size, start).trace(&rotations).collect::<Vec<_>>();
+            assert_eq!(
+                steps,
+                simulate(size, start, &rotations),
+                "size {size}, start {start}, rotations {rotations:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_rotations_match_simulation() {
+        let rotations = [1_000_000, -999_999, -1, 250_049, -3_000_001];
+        for (size, start) in [(1, 0), (100, 50), (100, -50), (7, -1_000_003), (1_000, 999)] {
+            let steps = Dial::new(size, start).trace(&rotations).collect::<Vec<_>>();
+            assert_eq!(steps, simulate(size, start, &rotations));
+        }
+    }
+
+    /// Turns the dial one click at a time.
+    fn simulate(size: isize, start: isize, rotations: &[isize]) -> Vec<Step> {
+        let mut position = start.rem_euclid(size);
+        rotations
+            .iter()
+            .map(|rotation| {
+                let mut zero_clicks = 0;
+                for _ in 0..rotation.unsigned_abs() {
+                    position = (position + rotation.signum()).rem_euclid(size);
+                    if position == 0 {
+                        zero_clicks += 1;
+                    }
+                }
+                Step {
+                    position,
+                    zero_clicks,
+                    ends_at_zero: position == 0,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,291 @@
+use anyhow::Result;
+use aoc_lib::{
+    finish_parse, normalize,
+    parsers::{comma_list, range},
+    Digits,
+};
+use nom::{IResult, Parser};
+use std::ops::RangeInclusive;
+
+const MAX_DIGITS: u32 = u64::MAX.ilog10() + 1;
+
+pub fn part_one(input: &[RangeInclusive<u64>]) -> u128 {
+    find_with_filter(input, Pattern::is_invalid_one)
+}
+
+pub fn part_two(input: &[RangeInclusive<u64>]) -> u128 {
+    find_with_filter(input, Pattern::is_invalid_two)
+}
+
+/// Parses the input and solves both parts, for the `aoc` runner.
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let input = parse(input)?;
+
+    Ok([part_one(&input).to_string(), part_two(&input).to_string()])
+}
+
+/// Sums the IDs in the ranges whose minimal repetition pattern matches `filter`.
+pub fn find_with_filter(input: &[RangeInclusive<u64>], filter: impl Fn(&Pattern) -> bool) -> u128 {
+    input
+        .iter()
+        .flat_map(|range| {
+            (1..=MAX_DIGITS).flat_map(move |digits| {
+                (1..=digits)
+                    .filter(move |period| digits.is_multiple_of(*period))
+                    .map(move |period| (range, digits, period))
+            })
+        })
+        .filter(|(_, digits, period)| {
+            filter(&Pattern {
+                period: *period,
+                repeats: digits / period,
+            })
+        })
+        .map(|(range, digits, period)| sum_minimal(range, digits, period))
+        .sum()
+}
+
+/// The shortest block an ID is made of, e.g. 121212 is `12` repeated three times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Repetition {
+    pub block: u64,
+    pub pattern: Pattern,
+}
+
+impl Repetition {
+    pub fn of(id: u64) -> Repetition {
+        let digits = id.digit_count();
+        (1..=digits)
+            .filter(|period| digits.is_multiple_of(*period))
+            .find_map(|period| {
+                let mut blocks = id.blocks(period)?;
+                let block = blocks.next()?;
+                blocks.all(|b| b == block).then_some(Repetition {
+                    block,
+                    pattern: Pattern {
+                        period,
+                        repeats: digits / period,
+                    },
+                })
+            })
+            .expect("every ID is its own block")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    pub period: u32,
+    pub repeats: u32,
+}
+
+impl Pattern {
+    /// A block repeated exactly twice, where the block itself may be repeated (1111 = 11 11).
+    fn is_invalid_one(&self) -> bool {
+        self.repeats.is_multiple_of(2)
+    }
+
+    fn is_invalid_two(&self) -> bool {
+        self.repeats >= 2
+    }
+}
+
+/// Sum of the IDs in `range` with `digits` digits whose shortest block has exactly `period` digits.
+///
+/// IDs built from a block of `period` digits include those whose block is itself repeated, so the
+/// sums for every divisor of `period` are combined by Möbius inversion.
+fn sum_minimal(range: &RangeInclusive<u64>, digits: u32, period: u32) -> u128 {
+    (1..=period)
+        .filter(|divisor| period.is_multiple_of(*divisor))
+        .map(|divisor| {
+            let sum = sum_periodic(range, digits, divisor) as i128;
+            mobius(period / divisor) as i128 * sum
+        })
+        .sum::<i128>() as u128
+}
+
+/// Sum of the IDs in `range` with `digits` digits that consist of a block of `period` digits
+/// repeated `digits / period` times.
+fn sum_periodic(range: &RangeInclusive<u64>, digits: u32, period: u32) -> u128 {
+    let lo = (*range.start() as u128).max(10u128.pow(digits - 1));
+    let hi = (*range.end() as u128).min(10u128.pow(digits) - 1);
+    if lo > hi {
+        return 0;
+    }
+
+    // every such ID is `block * repunit`, e.g. 123123 = 123 * 1001
+    let repunit = (10u128.pow(digits) - 1) / (10u128.pow(period) - 1);
+    let first = lo.div_ceil(repunit).max(10u128.pow(period - 1));
+    let last = (hi / repunit).min(10u128.pow(period) - 1);
+    if first > last {
+        return 0;
+    }
+
+    (first + last) * (last - first + 1) / 2 * repunit
+}
+
+fn mobius(mut n: u32) -> i32 {
+    let mut result = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        result = -result;
+    }
+
+    result
+}
+
+pub fn parse(input: &str) -> Result<Vec<RangeInclusive<u64>>> {
+    let input = normalize(input);
+    let result = finish_parse(&input, parse_list(&input))?;
+
+    Ok(result)
+}
+
+fn parse_list(input: &str) -> IResult<&str, Vec<RangeInclusive<u64>>> {
+    comma_list(range).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const DATA: &str = include_str!("input.txt");
+    const TESTDATA: &str = include_str!("test.txt");
+
+    #[test]
+    fn test_parse_one_testdata() -> Result<()> {
+        let res = part_one(&parse(TESTDATA)?);
+        assert_eq!(res, 1227775554);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_one() -> Result<()> {
+        let res = part_one(&parse(DATA)?);
+        assert_eq!(res, 53420042388);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two_testdata() -> Result<()> {
+        let input = parse(TESTDATA)?;
+        assert_eq!(part_two(&input), 4174379265);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf_testdata() -> Result<()> {
+        let input = TESTDATA.replace('\n', "  \r\n");
+        let input = parse(&input)?;
+        assert_eq!(part_one(&input), 1227775554);
+        assert_eq!(part_two(&input), 4174379265);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        let input = parse(DATA)?;
+        assert_eq!(part_two(&input), 69553832684);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..50 {
+            let digits = rng.random_range(1..=9);
+            let start = rng.random_range(0..10u64.pow(digits));
+            let range = start..=start + rng.random_range(0..20_000);
+
+            let input = [range.clone()];
+            let one = range
+                .clone()
+                .filter(is_invalid_one)
+                .map(u128::from)
+                .sum::<u128>();
+            let two = range.filter(is_invalid_two).map(u128::from).sum::<u128>();
+            assert_eq!(part_one(&input), one);
+            assert_eq!(part_two(&input), two);
+        }
+    }
+
+    #[test]
+    fn test_wide_ranges() {
+        assert_eq!(part_one(&[11..=99]), 11 * 45);
+        assert_eq!(part_two(&[100..=999]), 111 * 45);
+        // 1111 is counted once even though it repeats both "1" and "11"
+        assert_eq!(part_two(&[1000..=1200]), 1010 + 1111);
+
+        let everything = part_two(&[0..=u64::MAX]);
+        assert!(everything > part_one(&[0..=u64::MAX]));
+        assert_eq!(part_two(&[u64::MAX..=u64::MAX]), 0);
+    }
+
+    #[test]
+    fn test_repetition() {
+        let repetition = |block, period, repeats| Repetition {
+            block,
+            pattern: Pattern { period, repeats },
+        };
+        assert_eq!(Repetition::of(121212), repetition(12, 2, 3));
+        assert_eq!(Repetition::of(1111), repetition(1, 1, 4));
+        assert_eq!(Repetition::of(123), repetition(123, 3, 1));
+        assert_eq!(Repetition::of(7), repetition(7, 1, 1));
+        assert_eq!(Repetition::of(1010), repetition(10, 2, 2));
+        assert!(Repetition::of(1111).pattern.is_invalid_one());
+        assert!(!Repetition::of(121212).pattern.is_invalid_one());
+        assert!(Repetition::of(121212).pattern.is_invalid_two());
+    }
+
+    #[test]
+    fn test_custom_filters() {
+        let filters: [fn(&Pattern) -> bool; 4] = [
+            |p| p.repeats == 3,
+            |p| p.repeats >= 2 && p.period <= 2,
+            |p| p.repeats == 1,
+            |_| true,
+        ];
+        let mut rng = StdRng::seed_from_u64(31);
+        for _ in 0..20 {
+            let start = rng.random_range(0..10_000_000);
+            let input = [start..=start + rng.random_range(0..20_000)];
+            for filter in filters {
+                let expected = input[0]
+                    .clone()
+                    .filter(|id| filter(&Repetition::of(*id).pattern))
+                    .map(u128::from)
+                    .sum::<u128>();
+                assert_eq!(find_with_filter(&input, filter), expected);
+            }
+        }
+    }
+
+    fn is_invalid_one(id: &u64) -> bool {
+        id.blocks(id.digit_count() / 2)
+            .is_some_and(|mut blocks| blocks.len() == 2 && blocks.next() == blocks.next())
+    }
+
+    fn is_invalid_two(id: &u64) -> bool {
+        (1..=id.digit_count() / 2).any(|width| {
+            id.blocks(width).is_some_and(|mut blocks| {
+                let first = blocks.next();
+                blocks.all(|block| Some(block) == first)
+            })
+        })
+    }
+}
@@ -0,0 +1,274 @@
+use anyhow::{anyhow, bail, Result};
+use aoc_lib::{
+    finish_parse, normalize,
+    parsers::{digit, lines},
+    Digits,
+};
+use nom::{combinator::map, multi::many1, IResult, Parser};
+use std::fmt;
+
+pub fn part_one(input: &[Vec<u16>]) -> Result<u128> {
+    total_joltage(input, 2)
+}
+
+pub fn part_two(input: &[Vec<u16>]) -> Result<u128> {
+    total_joltage(input, 12)
+}
+
+/// Parses the input and solves both parts, for the `aoc` runner.
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let input = parse(input)?;
+
+    Ok([part_one(&input)?.to_string(), part_two(&input)?.to_string()])
+}
+
+pub fn total_joltage(input: &[Vec<u16>], length: usize) -> Result<u128> {
+    input.iter().enumerate().try_fold(0u128, |total, (i, row)| {
+        calculate_line(row, length)
+            .map_err(|e| anyhow!("{e} at line {}", i + 1))?
+            .value::<u128>()
+            .and_then(|value| total.checked_add(value))
+            .ok_or_else(|| anyhow!("joltage overflows a u128 at line {}", i + 1))
+    })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Selection {
+    pub indices: Vec<usize>,
+    pub digits: Vec<u8>,
+}
+
+impl Selection {
+    /// The selected digits as a number, or `None` if they do not fit in `T`.
+    pub fn value<T: Digits>(&self) -> Option<T> {
+        T::from_digits(self.digits.iter().copied())
+    }
+}
+
+/// Writes the selected digits, which works for any length.
+impl fmt::Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for digit in &self.digits {
+            write!(f, "{digit}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Renders the bank with a marker under every kept digit, followed by the kept positions.
+pub fn explain(input: &[u16], selection: &Selection) -> String {
+    let line = input
+        .iter()
+        .map(|digit| digit.to_string())
+        .collect::<String>();
+    let mut markers = vec![' '; input.len()];
+    for i in &selection.indices {
+        markers[*i] = '^';
+    }
+    let markers = markers.into_iter().collect::<String>();
+    let positions = selection
+        .indices
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "{line}\n{}\nkept {positions} -> {selection}",
+        markers.trim_end()
+    )
+}
+
+/// Picks the `length` digits, in order, that form the largest number.
+///
+/// A digit is dropped whenever a larger one follows it, as long as enough digits remain to fill
+/// `length`; every index is pushed and popped at most once. Fails if the bank has fewer than
+/// `length` digits.
+pub fn calculate_line(input: &[u16], length: usize) -> Result<Selection> {
+    if input.len() < length {
+        bail!("{length} digits to keep from a bank of {}", input.len());
+    }
+    let mut drops = input.len() - length;
+    let mut indices: Vec<usize> = Vec::with_capacity(input.len());
+    for (i, digit) in input.iter().enumerate() {
+        while drops > 0
+            && let Some(&top) = indices.last()
+            && input[top] < *digit
+        {
+            indices.pop();
+            drops -= 1;
+        }
+        indices.push(i);
+    }
+    indices.truncate(length);
+
+    let digits = indices.iter().map(|i| input[*i] as u8).collect();
+
+    Ok(Selection { indices, digits })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<u16>>> {
+    let input = normalize(input);
+    let result = finish_parse(&input, parse_input(&input))?;
+
+    Ok(result)
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Vec<u16>>> {
+    lines(many1(map(digit, u16::from))).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const DATA: &str = include_str!("input.txt");
+    const TESTDATA: &str = include_str!("test.txt");
+
+    #[test]
+    fn test_parse_one_testdata() -> Result<()> {
+        let res = part_one(&parse(TESTDATA)?)?;
+        assert_eq!(res, 357);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_one() -> Result<()> {
+        let res = part_one(&parse(DATA)?)?;
+        assert_eq!(res, 17408);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two_testdata() -> Result<()> {
+        let input = parse(TESTDATA)?;
+        assert_eq!(part_two(&input)?, 3121910778619);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf_testdata() -> Result<()> {
+        let input = TESTDATA.replace('\n', " \r\n");
+        let input = parse(&input)?;
+        assert_eq!(part_one(&input)?, 357);
+        assert_eq!(part_two(&input)?, 3121910778619);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        let input = parse(DATA)?;
+        assert_eq!(part_two(&input)?, 172740584266849);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_line() -> Result<()> {
+        let line = [8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1];
+        assert_eq!(
+            calculate_line(&line, 2)?,
+            Selection {
+                indices: vec![6, 11],
+                digits: vec![9, 2],
+            }
+        );
+        assert_eq!(calculate_line(&line, 12)?.value(), Some(888911112111u64));
+        assert_eq!(calculate_line(&[3, 1], 2)?.indices, vec![0, 1]);
+        assert_eq!(calculate_line(&[3, 1], 0)?.value(), Some(0u64));
+        assert_eq!(
+            calculate_line(&[3, 1], 5).unwrap_err().to_string(),
+            "5 digits to keep from a bank of 2"
+        );
+
+        let err = solve("987\n9\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2 digits to keep from a bank of 1 at line 2"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_line_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..500 {
+            let len = rng.random_range(1..=10);
+            let line = (0..len)
+                .map(|_| rng.random_range(0..=9))
+                .collect::<Vec<u16>>();
+            let length = rng.random_range(1..=len);
+
+            let selection = calculate_line(&line, length).unwrap();
+            assert_eq!(
+                selection.value(),
+                Some(brute_force(&line, length)),
+                "{line:?} {length}"
+            );
+            assert_eq!(selection.indices.len(), length);
+            assert!(selection.indices.windows(2).all(|w| w[0] < w[1]));
+            let digits = selection.indices.iter().map(|i| line[*i] as u8);
+            assert!(digits.eq(selection.digits.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn test_long_joltage() -> Result<()> {
+        let input = parse(TESTDATA)?;
+
+        let line = calculate_line(&input[0], 15)?;
+        assert_eq!(line.value::<u64>(), Some(987654321111111));
+        assert_eq!(line.to_string(), "987654321111111");
+
+        let long = vec![9; 50];
+        let line = calculate_line(&long, 40)?;
+        assert_eq!(line.value::<u64>(), None);
+        assert_eq!(line.value::<u128>(), None);
+        assert_eq!(line.to_string(), "9".repeat(40));
+        assert!(total_joltage(&[long], 40).is_err());
+        assert_eq!(total_joltage(&[vec![9; 30]], 20)?, 99999999999999999999);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() -> Result<()> {
+        let input = parse(TESTDATA)?;
+
+        let explained = input
+            .iter()
+            .map(|row| Ok(explain(row, &calculate_line(row, 2)?)))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(explained[0], "987654321111111\n^^\nkept 0, 1 -> 98");
+        assert_eq!(
+            explained[1],
+            "811111111111119\n^             ^\nkept 0, 14 -> 89"
+        );
+        assert_eq!(
+            explained[3],
+            "818181911112111\n      ^    ^\nkept 6, 11 -> 92"
+        );
+
+        Ok(())
+    }
+
+    fn brute_force(line: &[u16], length: usize) -> u64 {
+        (0u32..1 << line.len())
+            .filter(|mask| mask.count_ones() as usize == length)
+            .map(|mask| {
+                line.iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << i) != 0)
+                    .fold(0, |acc, (_, digit)| acc * 10 + *digit as u64)
+            })
+            .max()
+            .unwrap()
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::Result;
+use aoc_lib::{finish_parse, normalize, parsers::char_grid};
+use nom::{character::complete::one_of, combinator::map, IResult, Parser};
+
+pub fn part_one(input: &Grid) -> usize {
+    rolls_to_remove(input).len()
+}
+
+pub fn rolls_to_remove(input: &Grid) -> Vec<(usize, usize)> {
+    input
+        .data
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter().enumerate().filter_map(move |(x, val)| {
+                if *val && input.count_neighbours(x, y) < 4 {
+                    Some((x, y))
+                } else {
+                    None
+                }
+            })
+        })
+        .collect::<Vec<_>>()
+}
+
+pub fn part_two(input: &mut Grid) -> usize {
+    let mut count = 0;
+    loop {
+        let rolls = rolls_to_remove(input);
+        if rolls.is_empty() {
+            break;
+        }
+        count += rolls.len();
+
+        input.remove(&rolls);
+    }
+
+    count
+}
+
+/// Parses the input and solves both parts, for the `aoc` runner.
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let mut grid = parse(input)?;
+
+    Ok([part_one(&grid).to_string(), part_two(&mut grid).to_string()])
+}
+
+#[derive(Debug)]
+pub struct Grid {
+    data: Vec<Vec<bool>>,
+}
+
+impl Grid {
+    pub fn height(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.data[0].len()
+    }
+
+    pub fn count_neighbours(&self, x: usize, y: usize) -> usize {
+        let mut count = 0;
+        if y > 0 {
+            if x > 0 && self.data[y - 1][x - 1] {
+                count += 1;
+            }
+            if self.data[y - 1][x] {
+                count += 1;
+            }
+            if x < self.width() - 1 && self.data[y - 1][x + 1] {
+                count += 1;
+            }
+        }
+        if x > 0 && self.data[y][x - 1] {
+            count += 1;
+        }
+        if x < self.width() - 1 && self.data[y][x + 1] {
+            count += 1;
+        }
+        if y < self.height() - 1 {
+            if x > 0 && self.data[y + 1][x - 1] {
+                count += 1;
+            }
+            if self.data[y + 1][x] {
+                count += 1;
+            }
+            if x < self.width() - 1 && self.data[y + 1][x + 1] {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub fn remove(&mut self, rolls: &[(usize, usize)]) {
+        for (x, y) in rolls {
+            self.data[*y][*x] = false;
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Grid> {
+    let input = normalize(input);
+    let result = finish_parse(&input, parse_grid(&input))?;
+
+    Ok(result)
+}
+
+fn parse_grid(input: &str) -> IResult<&str, Grid> {
+    map(char_grid(parse_cell), |data| Grid { data }).parse(input)
+}
+
+fn parse_cell(input: &str) -> IResult<&str, bool> {
+    map(one_of(".@"), |c| c == '@').parse(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_lib::ParseError;
+
+    const DATA: &str = include_str!("input.txt");
+    const TESTDATA: &str = include_str!("test.txt");
+
+    #[test]
+    fn test_parse_one_testdata() -> Result<()> {
+        let grid = parse(TESTDATA)?;
+
+        grid.count_neighbours(0, 2);
+
+        let res = part_one(&grid);
+        assert_eq!(res, 13);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_one() -> Result<()> {
+        let grid = parse(DATA)?;
+
+        let res = part_one(&grid);
+        assert_eq!(res, 1464);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_two_testdata() -> Result<()> {
+        let mut grid = parse(TESTDATA)?;
+
+        let res = part_two(&mut grid);
+        assert_eq!(res, 43);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf_testdata() -> Result<()> {
+        let input = TESTDATA.replace('\n', "\t\r\n");
+        let mut grid = parse(&input)?;
+        assert_eq!(part_one(&grid), 13);
+        assert_eq!(part_two(&mut grid), 43);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_two() -> Result<()> {
+        let mut grid = parse(DATA)?;
+
+        let res = part_two(&mut grid);
+        assert_eq!(res, 8409);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ragged_grid() -> Result<()> {
+        for (input, line, column) in [("@@@\n@\n@@@\n", 2, 2), ("@.@\n@@.@\n", 2, 4)] {
+            let err = parse(input).unwrap_err().downcast::<ParseError>()?;
+            assert_eq!((err.line, err.column), (line, column), "{input:?}");
+            assert_eq!(err.message, "row is not as wide as the first row");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use anyhow::Result;
+use aoc_lib::{
+    finish_parse, normalize,
+    parsers::{blank_line, integer, lines, range},
+    IntervalSet,
+};
+use nom::{sequence::separated_pair, IResult, Parser};
+use std::{fmt, ops::RangeInclusive};
+
+pub fn part_one(ranges: &Ranges, ingredients: &[u64]) -> usize {
+    ingredients
+        .iter()
+        .filter(|id| ranges.merged.contains(**id))
+        .count()
+}
+
+pub fn part_two(ranges: &Ranges) -> u128 {
+    ranges.merged.len()
+}
+
+/// Parses the input and solves both parts, for the `aoc` runner.
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let (ranges, ingredients) = parse(input)?;
+
+    Ok([
+        part_one(&ranges, &ingredients).to_string(),
+        part_two(&ranges).to_string(),
+    ])
+}
+
+pub fn report(ranges: &Ranges, ingredients: &[u64]) -> Report {
+    // original ranges grouped by the merged range they ended up in
+    let mut groups = vec![vec![]; ranges.merged.ranges().len()];
+    for (i, range) in ranges.original.iter().enumerate() {
+        if let Some(pos) = ranges.merged.position(*range.start()) {
+            groups[pos].push(i);
+        }
+    }
+
+    let entries = ingredients
+        .iter()
+        .map(|&id| {
+            let merged = ranges.merged.position(id);
+            let sources = merged
+                .map(|pos| {
+                    groups[pos]
+                        .iter()
+                        .filter(|&&i| ranges.original[i].contains(&id))
+                        .map(|&i| (i, ranges.original[i].clone()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Attribution {
+                id,
+                merged: merged.map(|pos| ranges.merged.ranges()[pos].clone()),
+                sources,
+            }
+        })
+        .collect();
+
+    Report { entries }
+}
+
+#[derive(Debug)]
+pub struct Ranges {
+    pub original: Vec<RangeInclusive<u64>>,
+    pub merged: IntervalSet<u64>,
+}
+
+impl From<Vec<RangeInclusive<u64>>> for Ranges {
+    fn from(original: Vec<RangeInclusive<u64>>) -> Ranges {
+        let merged = original.iter().cloned().collect();
+
+        Ranges { original, merged }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Attribution {
+    pub id: u64,
+    pub merged: Option<RangeInclusive<u64>>,
+    /// Original ranges covering the ID, with their index in the input.
+    pub sources: Vec<(usize, RangeInclusive<u64>)>,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub entries: Vec<Attribution>,
+}
+
+impl Report {
+    pub fn spoiled(&self) -> Vec<u64> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.merged.is_none())
+            .map(|entry| entry.id)
+            .collect()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            match &entry.merged {
+                Some(merged) => {
+                    let sources = entry
+                        .sources
+                        .iter()
+                        .map(|(i, r)| format!("#{} {}-{}", i + 1, r.start(), r.end()))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    writeln!(
+                        f,
+                        "{}: fresh in {}-{} (from {sources})",
+                        entry.id,
+                        merged.start(),
+                        merged.end()
+                    )?;
+                }
+                None => writeln!(f, "{}: spoiled", entry.id)?,
+            }
+        }
+        let spoiled = self
+            .spoiled()
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(f, "Spoiled: {spoiled}")
+    }
+}
+
+pub fn parse(input: &str) -> Result<(Ranges, Vec<u64>)> {
+    let input = normalize(input);
+    let (ranges, ingredients) = finish_parse(&input, parse_list(&input))?;
+
+    Ok((ranges.into(), ingredients))
+}
+
+fn parse_list(input: &str) -> IResult<&str, (Vec<RangeInclusive<u64>>, Vec<u64>)> {
+    separated_pair(lines(range), blank_line, lines(integer)).parse(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const DATA: &str = include_str!("input.txt");
+    const TESTDATA: &str = include_str!("test.txt");
+
+    #[test]
+    fn test_parse_one_testdata() -> Result<()> {
+        let (ranges, ingredients) = parse(TESTDATA)?;
+
+        let res = part_one(&ranges, &ingredients);
+        assert_eq!(res, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_one() -> Result<()> {
+        let (ranges, ingredients) = parse(DATA)?;
+
+        let res = part_one(&ranges, &ingredients);
+        assert_eq!(res, 517);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_two_testdata() -> Result<()> {
+        let (ranges, _) = parse(TESTDATA)?;
+
+        let res = part_two(&ranges);
+        assert_eq!(res, 14);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_two() -> Result<()> {
+        let (ranges, _) = parse(DATA)?;
+
+        let res = part_two(&ranges);
+        assert_eq!(res, 336173027056994);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf_testdata() -> Result<()> {
+        let input = TESTDATA.replace('\n', " \r\n");
+        let (ranges, ingredients) = parse(&input)?;
+        assert_eq!(part_one(&ranges, &ingredients), 3);
+        assert_eq!(part_two(&ranges), 14);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_one_matches_linear_scan() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for width in [10, 1_000, 1_000_000_000, u64::MAX / 4] {
+            let ranges = (0..1_000)
+                .map(|_| {
+                    let start = rng.random_range(0..u64::MAX - width);
+                    start..=start + rng.random_range(0..width)
+                })
+                .collect::<Vec<_>>();
+            let mut ingredients = (0..5_000).map(|_| rng.random::<u64>()).collect::<Vec<_>>();
+            ingredients.extend(
+                ranges
+                    .iter()
+                    .flat_map(|r| [*r.start(), *r.end(), r.end() + 1]),
+            );
+            ingredients.extend([0, u64::MAX]);
+
+            let expected = ingredients
+                .iter()
+                .filter(|id| ranges.iter().any(|range| range.contains(id)))
+                .count();
+            let ranges = Ranges::from(ranges);
+            assert_eq!(part_one(&ranges, &ingredients), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("3-5\n10-14\n\n1\n5x\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unexpected trailing input at line 5, column 2\n  |\n5 | 5x\n  |  ^"
+        );
+
+        let err = parse("3-5\n10_14\n\n1\n").unwrap_err();
+        assert!(err.to_string().starts_with("unexpected input"));
+        assert!(err.to_string().contains("2 | 10_14"));
+    }
+
+    #[test]
+    fn test_report_testdata() -> Result<()> {
+        let (ranges, ingredients) = parse(TESTDATA)?;
+
+        let report = report(&ranges, &ingredients);
+        assert_eq!(report.spoiled(), vec![1, 8, 32]);
+        assert_eq!(
+            report.entries[4],
+            Attribution {
+                id: 17,
+                merged: Some(10..=20),
+                sources: vec![(2, 16..=20), (3, 12..=18)],
+            }
+        );
+        assert_eq!(
+            report.to_string(),
+            "1: spoiled\n\
+             5: fresh in 3-5 (from #1 3-5)\n\
+             8: spoiled\n\
+             11: fresh in 10-20 (from #2 10-14)\n\
+             17: fresh in 10-20 (from #3 16-20, #4 12-18)\n\
+             32: spoiled\n\
+             Spoiled: 1, 8, 32\n"
+        );
+
+        Ok(())
+    }
+}
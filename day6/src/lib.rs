@@ -0,0 +1,673 @@
+use anyhow::{anyhow, Result};
+use aoc_lib::{finish_parse, normalize, parsers::lines, Count, Digits, ParseError};
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending, one_of},
+    combinator::{map, value, verify},
+    multi::many1,
+    sequence::separated_pair,
+    IResult, Parser,
+};
+use std::{fmt, ops::Range};
+
+pub fn part_one(worksheet: &Worksheet) -> Result<u64> {
+    worksheet.total(Reading::Rows)
+}
+
+pub fn part_two(worksheet: &Worksheet) -> Result<u64> {
+    worksheet.total(Reading::Columns)
+}
+
+/// Parses the input and solves both parts, for the `aoc` runner.
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let worksheet = parse(input)?;
+
+    Ok([
+        part_one(&worksheet)?.to_string(),
+        part_two(&worksheet)?.to_string(),
+    ])
+}
+
+/// How the digits of a block are read into numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reading {
+    /// One number per line, left to right.
+    Rows,
+    /// One number per column, top to bottom, starting from the rightmost column.
+    Columns,
+}
+
+#[derive(Debug)]
+pub struct Worksheet {
+    pub blocks: Vec<Block>,
+}
+
+impl Worksheet {
+    /// Splits the number lines into blocks at every column that is blank on all lines, including
+    /// the operator line. Positions past the end of a line count as blank.
+    ///
+    /// `input` is the text `lines` and `operators` were parsed from, used to locate errors.
+    fn new(
+        input: &str,
+        lines: &[Vec<char>],
+        operators: &[Option<Operator>],
+    ) -> Result<Worksheet, ParseError> {
+        let width = lines
+            .iter()
+            .map(|line| line.len())
+            .chain([operators.len()])
+            .max()
+            .unwrap_or(0);
+        let is_blank = |column: usize| {
+            lines
+                .iter()
+                .all(|line| line.get(column).is_none_or(|c| *c == ' '))
+                && operators.get(column).is_none_or(|o| o.is_none())
+        };
+
+        let mut blocks = vec![];
+        let mut column = 0;
+        while column < width {
+            if is_blank(column) {
+                column += 1;
+                continue;
+            }
+            let start = column;
+            while column < width && !is_blank(column) {
+                column += 1;
+            }
+            blocks.push(Block::new(input, lines, operators, start..column)?);
+        }
+
+        Ok(Worksheet { blocks })
+    }
+
+    pub fn total<T: Number>(&self, reading: Reading) -> Result<T> {
+        self.blocks.iter().try_fold(T::zero(), |total, block| {
+            let result = block
+                .problem(reading)
+                .and_then(|problem| problem.calculate::<T>())
+                .map_err(|e| anyhow!("problem at column {}: {e}", block.column + 1))?;
+
+            total
+                .checked_add(&result)
+                .ok_or_else(|| anyhow!("total overflows a {}", T::NAME))
+        })
+    }
+}
+
+/// One problem as written on the worksheet: the characters of its columns on every number line,
+/// padded with spaces to the width of the block.
+#[derive(Debug)]
+pub struct Block {
+    pub column: usize,
+    pub rows: Vec<Vec<char>>,
+    pub operator: Operator,
+}
+
+impl Block {
+    /// Cuts the block out of `columns`, checking that every number line holds exactly one number
+    /// and that the operator sits under the first column.
+    fn new(
+        input: &str,
+        lines: &[Vec<char>],
+        operators: &[Option<Operator>],
+        columns: Range<usize>,
+    ) -> Result<Block, ParseError> {
+        let column = columns.start;
+        // lines may end before the block, so point at the end of the line instead
+        let error = |line: usize, offset: usize, message: String| {
+            let line_start = input
+                .lines()
+                .take(line)
+                .map(|line| line.len() + 1)
+                .sum::<usize>();
+            let line_len = input.lines().nth(line).map_or(0, str::len);
+            ParseError::new(input, line_start + line_len.min(column + offset), message)
+        };
+
+        let rows = lines
+            .iter()
+            .map(|line| {
+                columns
+                    .clone()
+                    .map(|c| line.get(c).copied().unwrap_or(' '))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for (i, row) in rows.iter().enumerate() {
+            let Some(first) = row.iter().position(|c| *c != ' ') else {
+                return Err(error(
+                    i,
+                    0,
+                    format!("no number for the problem at column {}", column + 1),
+                ));
+            };
+            let end = first + row[first..].iter().take_while(|c| **c != ' ').count();
+            if row[end..].iter().any(|c| *c != ' ') {
+                return Err(error(
+                    i,
+                    end,
+                    format!(
+                        "several numbers in the problem at column {}, which needs a blank column between them",
+                        column + 1
+                    ),
+                ));
+            }
+        }
+
+        let mut found = columns
+            .clone()
+            .filter_map(|c| operators.get(c).copied().flatten().map(|o| (c, o)));
+        let operator = match (found.next(), found.next()) {
+            (Some((c, operator)), None) if c == column => operator,
+            (Some((c, _)), None) => {
+                return Err(error(
+                    lines.len(),
+                    c - column,
+                    format!(
+                        "operator belongs under the first column of its problem, column {}",
+                        column + 1
+                    ),
+                ));
+            }
+            (None, _) => {
+                return Err(error(
+                    lines.len(),
+                    0,
+                    format!("no operator for the problem at column {}", column + 1),
+                ));
+            }
+            (Some(_), Some((c, _))) => {
+                return Err(error(
+                    lines.len(),
+                    c - column,
+                    format!("several operators for the problem at column {}", column + 1),
+                ));
+            }
+        };
+
+        Ok(Block {
+            column,
+            rows,
+            operator,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.len())
+    }
+
+    pub fn numbers(&self, reading: Reading) -> Result<Vec<u64>> {
+        match reading {
+            Reading::Rows => self
+                .rows
+                .iter()
+                .filter_map(|row| read_number(row.iter().copied()).transpose())
+                .collect(),
+            Reading::Columns => (0..self.width())
+                .rev()
+                .filter_map(|c| read_number(self.rows.iter().map(|row| row[c])).transpose())
+                .collect(),
+        }
+    }
+
+    pub fn problem(&self, reading: Reading) -> Result<Problem> {
+        Ok(Problem {
+            numbers: self.numbers(reading)?,
+            operator: self.operator,
+        })
+    }
+}
+
+/// Renders the block's text next to its part one and part two numbers, each written as a vertical
+/// sum with the operator in front of the last number and the result under a rule.
+pub fn explain(block: &Block) -> Result<String> {
+    let mut text = block
+        .rows
+        .iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>();
+    text.push(format!(
+        "{:<width$}",
+        block.operator.to_string(),
+        width = block.width()
+    ));
+    let columns = [
+        text,
+        evaluation(&block.problem(Reading::Rows)?),
+        evaluation(&block.problem(Reading::Columns)?),
+    ];
+
+    let height = columns.iter().map(|lines| lines.len()).max().unwrap_or(0);
+    let mut result = vec![format!("column {}", block.column + 1)];
+    for i in 0..height {
+        let line = columns
+            .iter()
+            .map(|lines| {
+                lines
+                    .get(i)
+                    .cloned()
+                    .unwrap_or_else(|| " ".repeat(lines[0].len()))
+            })
+            .collect::<Vec<_>>()
+            .join("   ");
+        result.push(line.trim_end().to_string());
+    }
+
+    Ok(result.join("\n"))
+}
+
+fn evaluation(problem: &Problem) -> Vec<String> {
+    let mut lines = problem
+        .numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>();
+    if let Some(last) = lines.last_mut() {
+        *last = format!("{} {last}", problem.operator);
+    }
+    let result = match problem.calculate::<u64>() {
+        Ok(result) => result.to_string(),
+        Err(e) => e.to_string(),
+    };
+    let width = lines
+        .iter()
+        .chain([&result])
+        .map(|line| line.len())
+        .max()
+        .unwrap_or(0);
+    lines.push("-".repeat(width));
+    lines.push(result);
+
+    lines.iter().map(|line| format!("{line:>width$}")).collect()
+}
+
+/// Reads the digits in `chars` as one number, skipping spaces, or `None` if there are none.
+fn read_number(chars: impl Iterator<Item = char>) -> Result<Option<u64>> {
+    let mut number = None;
+    for digit in chars.filter_map(|c| c.to_digit(10)) {
+        let value = number
+            .unwrap_or(0u64)
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(digit as u64))
+            .ok_or_else(|| anyhow!("number overflows a u64"))?;
+        number = Some(value);
+    }
+
+    Ok(number)
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    pub numbers: Vec<u64>,
+    pub operator: Operator,
+}
+
+impl Problem {
+    /// Folds the numbers left to right with the operator, failing instead of wrapping around.
+    pub fn calculate<T: Number>(&self) -> Result<T> {
+        let mut numbers = self.numbers.iter().map(|n| T::from(*n));
+        let first = match self.operator {
+            Operator::Add => T::zero(),
+            Operator::Mul => T::one(),
+            _ => numbers.next().ok_or_else(|| anyhow!("no numbers"))?,
+        };
+
+        numbers.try_fold(first, |acc, n| {
+            self.operator
+                .apply(acc, n)
+                .ok_or_else(|| match self.operator {
+                    Operator::Sub => anyhow!("result goes below zero"),
+                    Operator::Div => anyhow!("division by zero"),
+                    _ => anyhow!("result overflows a {}", T::NAME),
+                })
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    /// `+`
+    Add,
+    /// `-`, subtracting every later number from the first.
+    Sub,
+    /// `*`
+    Mul,
+    /// `/`, dividing the first number by every later one, rounding down.
+    Div,
+    /// `<`, the smallest number.
+    Min,
+    /// `>`, the largest number.
+    Max,
+    /// `|`, writing the numbers one after the other.
+    Concat,
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Operator::Add => '+',
+            Operator::Sub => '-',
+            Operator::Mul => '*',
+            Operator::Div => '/',
+            Operator::Min => '<',
+            Operator::Max => '>',
+            Operator::Concat => '|',
+        };
+
+        write!(f, "{symbol}")
+    }
+}
+
+impl Operator {
+    fn apply<T: Number>(self, a: T, b: T) -> Option<T> {
+        match self {
+            Operator::Add => a.checked_add(&b),
+            Operator::Sub => a.checked_sub(b),
+            Operator::Mul => a.checked_mul(b),
+            Operator::Div => a.checked_div(b),
+            Operator::Min => Some(a.min(b)),
+            Operator::Max => Some(a.max(b)),
+            Operator::Concat => a.concat(b),
+        }
+    }
+}
+
+/// Result types for evaluating problems, adding through [`Count`]. Every operation returns `None`
+/// where the result is not representable, including division by zero.
+pub trait Number: Count + Ord + From<u64> + fmt::Display {
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div(self, other: Self) -> Option<Self>;
+    fn concat(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_number {
+    ($($t:ident),*) => {
+        $(
+            impl Number for $t {
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    <$t>::checked_div(self, other)
+                }
+
+                fn concat(self, other: Self) -> Option<Self> {
+                    Digits::concat(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(u64, u128);
+
+#[cfg(feature = "bigint")]
+impl Number for aoc_lib::BigUint {
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        (self >= other).then(|| self - other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_div(self, other: Self) -> Option<Self> {
+        (other != Self::ZERO).then(|| self / other)
+    }
+
+    fn concat(self, other: Self) -> Option<Self> {
+        let digits = other.to_string().len() as u32;
+        Some(self * Self::from(10u64).pow(digits) + other)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Worksheet> {
+    let input = normalize(input);
+    let (lines, operators) = finish_parse(&input, parse_input(&input))?;
+
+    let worksheet = Worksheet::new(&input, &lines, &operators)?;
+
+    Ok(worksheet)
+}
+
+type Lines = (Vec<Vec<char>>, Vec<Option<Operator>>);
+
+fn parse_input(input: &str) -> IResult<&str, Lines> {
+    separated_pair(lines(parse_numbers), line_ending, parse_operators).parse(input)
+}
+
+/// A line of digits and spaces, which needs at least one digit so an operator line that starts
+/// with spaces is not taken for it.
+fn parse_numbers(input: &str) -> IResult<&str, Vec<char>> {
+    verify(many1(one_of("1234567890 ")), |row: &[char]| {
+        row.iter().any(char::is_ascii_digit)
+    })
+    .parse(input)
+}
+
+fn parse_operators(input: &str) -> IResult<&str, Vec<Option<Operator>>> {
+    many1(alt((map(parse_operator, Some), value(None, char(' '))))).parse(input)
+}
+
+fn parse_operator(input: &str) -> IResult<&str, Operator> {
+    alt((
+        value(Operator::Add, char('+')),
+        value(Operator::Sub, char('-')),
+        value(Operator::Mul, char('*')),
+        value(Operator::Div, char('/')),
+        value(Operator::Min, char('<')),
+        value(Operator::Max, char('>')),
+        value(Operator::Concat, char('|')),
+    ))
+    .parse(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DATA: &str = include_str!("input.txt");
+    const TESTDATA: &str = include_str!("test.txt");
+
+    #[test]
+    fn test_parse_one_testdata() -> Result<()> {
+        let worksheet = parse(TESTDATA)?;
+
+        let res = part_one(&worksheet)?;
+        assert_eq!(res, 4277556);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_one() -> Result<()> {
+        let worksheet = parse(DATA)?;
+
+        let res = part_one(&worksheet)?;
+        assert_eq!(res, 4449991244405);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_two_testdata() -> Result<()> {
+        let worksheet = parse(TESTDATA)?;
+
+        let res = part_two(&worksheet)?;
+        assert_eq!(res, 3263827);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_two() -> Result<()> {
+        let worksheet = parse(DATA)?;
+
+        let res = part_two(&worksheet)?;
+        assert_eq!(res, 9348430857627);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf_testdata() -> Result<()> {
+        let input = TESTDATA.replace('\n', "\t\r\n");
+        let worksheet = parse(&input)?;
+        assert_eq!(part_one(&worksheet)?, 4277556);
+        assert_eq!(part_two(&worksheet)?, 3263827);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_worksheet_testdata() -> Result<()> {
+        let worksheet = parse(TESTDATA)?;
+
+        let columns = worksheet
+            .blocks
+            .iter()
+            .map(|block| (block.width(), block.operator))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            vec![
+                (3, Operator::Mul),
+                (3, Operator::Add),
+                (3, Operator::Mul),
+                (3, Operator::Add)
+            ]
+        );
+        let first = &worksheet.blocks[0];
+        assert_eq!(first.numbers(Reading::Rows)?, vec![123, 45, 6]);
+        assert_eq!(first.numbers(Reading::Columns)?, vec![356, 24, 1]);
+        let last = &worksheet.blocks[3];
+        assert_eq!(last.rows[0], vec!['6', '4', ' ']);
+        assert_eq!(last.numbers(Reading::Columns)?, vec![4, 431, 623]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_misaligned_worksheet() -> Result<()> {
+        for (input, line, column, message) in [
+            ("12  3\n 4 56\n*", 3, 2, "no operator for the problem at column 4"),
+            ("12  3\n 4 56\n*  ++", 3, 5, "several operators for the problem at column 4"),
+            (
+                "12  3\n 4 56\n*   +",
+                3,
+                5,
+                "operator belongs under the first column of its problem, column 4",
+            ),
+            ("12 3\n 4\n*  +", 2, 3, "no number for the problem at column 4"),
+            (
+                "12  3\n4 556\n*   +",
+                1,
+                3,
+                "several numbers in the problem at column 1, which needs a blank column between them",
+            ),
+        ] {
+            let err = parse(input).unwrap_err().downcast::<ParseError>()?;
+            assert_eq!((err.line, err.column, err.message.as_str()), (line, column, message), "{input:?}");
+        }
+
+        let err = parse("123 328\n 45 64 \n  6 98\n *  +").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "operator belongs under the first column of its problem, column 1 at line 4, column 2\n  |\n4 |  *  +\n  |  ^"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_operators() -> Result<()> {
+        let worksheet = parse("20 20 20 20 20 7\n 4  4  3 30 34 7\n-  /  <  >  |  /")?;
+
+        let results = worksheet
+            .blocks
+            .iter()
+            .map(|block| block.problem(Reading::Rows)?.calculate())
+            .collect::<Result<Vec<u64>>>()?;
+        assert_eq!(results, vec![16, 5, 3, 30, 2034, 1]);
+        assert_eq!(part_one(&worksheet)?, 2089);
+        assert_eq!(part_two(&worksheet)?, 2 + 2 + 2 + 23 + 423 + 77);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_arithmetic() -> Result<()> {
+        for (input, message) in [
+            (
+                "1  5\n2  7\n+  -",
+                "problem at column 4: result goes below zero",
+            ),
+            ("5\n0\n/", "problem at column 1: division by zero"),
+            (
+                "1 99999999999\n2 99999999999\n+ *",
+                "problem at column 3: result overflows a u64",
+            ),
+            (
+                "99999999999999999999\n*",
+                "problem at column 1: number overflows a u64",
+            ),
+        ] {
+            let err = part_one(&parse(input)?).unwrap_err();
+            assert_eq!(err.to_string(), message, "{input:?}");
+        }
+
+        let worksheet = parse("99999999999\n99999999999\n*")?;
+        assert_eq!(
+            worksheet.total::<u128>(Reading::Rows)?,
+            9999999999800000000001
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_bigint() -> Result<()> {
+        let line = "9999999999 9999999999\n";
+        let input = line.repeat(4) + "*          |";
+        let worksheet = parse(&input)?;
+        assert!(worksheet.total::<u128>(Reading::Rows).is_err());
+        assert_eq!(
+            worksheet
+                .total::<aoc_lib::BigUint>(Reading::Rows)?
+                .to_string(),
+            "19999999996000000000599999999960000000000"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_testdata() -> Result<()> {
+        let worksheet = parse(TESTDATA)?;
+
+        assert_eq!(
+            explain(&worksheet.blocks[0])?,
+            [
+                "column 1",
+                "123     123    356",
+                " 45      45     24",
+                "  6     * 6    * 1",
+                "*     -----   ----",
+                "      33210   8544",
+            ]
+            .join("\n")
+        );
+        assert!(explain(&worksheet.blocks[3])?.starts_with("column 13\n64 "));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,732 @@
+use anyhow::{anyhow, bail, Result};
+use aoc_lib::{finish_parse, normalize, parsers::char_grid, Count};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{map, value},
+    IResult, Parser,
+};
+
+pub fn part_one(grid: &Grid) -> Result<usize> {
+    if grid.is_downward() {
+        Ok(sweep::<bool>(grid)?.splits)
+    } else {
+        Ok(trace(grid))
+    }
+}
+
+pub fn part_two(grid: &Grid) -> Result<u128> {
+    if grid.is_downward() {
+        sweep::<u128>(grid)?.timelines()
+    } else {
+        timelines(grid)
+    }
+}
+
+/// Parses the input and solves both parts, for the `aoc` runner.
+pub fn solve(input: &str) -> Result<[String; 2]> {
+    let grid = parse(input)?;
+
+    Ok([part_one(&grid)?.to_string(), part_two(&grid)?.to_string()])
+}
+
+/// Follows the beams one row at a time, keeping a count for every column instead of a list of
+/// beams. With `bool` counts this only tracks where beams are; with integers it counts timelines.
+///
+/// Only handles grids where every beam travels down, see [`Grid::is_downward`].
+pub fn sweep<C: Count>(grid: &Grid) -> Result<Sweep<C>> {
+    let (width, height) = grid.size;
+    let mut counts = vec![C::zero(); width];
+    let mut next = counts.clone();
+    let mut sources = grid.sources.iter().peekable();
+
+    let mut splits = 0;
+    for y in 0..height {
+        let row = &grid.values[y];
+        for (x, count) in counts.iter().enumerate() {
+            if count.is_zero() {
+                continue;
+            }
+            let targets = if row[x] == Value::Splitter {
+                splits += 1;
+                [x.checked_sub(1), Some(x + 1).filter(|x| *x < width)]
+            } else {
+                [Some(x), None]
+            };
+            for target in targets.into_iter().flatten() {
+                next[target] = next[target]
+                    .checked_add(count)
+                    .ok_or_else(|| anyhow!("timelines overflow a {} in row {}", C::NAME, y + 1))?;
+            }
+        }
+        std::mem::swap(&mut counts, &mut next);
+        next.fill(C::zero());
+
+        while let Some((x, _)) = sources.next_if(|(_, row)| *row == y) {
+            counts[*x] = counts[*x]
+                .checked_add(&C::one())
+                .ok_or_else(|| anyhow!("timelines overflow a {} in row {}", C::NAME, y + 1))?;
+        }
+    }
+
+    Ok(Sweep {
+        splits,
+        exits: counts,
+    })
+}
+
+#[derive(Debug)]
+pub struct Sweep<C> {
+    /// The number of splitters hit by a beam.
+    pub splits: usize,
+    /// The count for every column after the last row.
+    pub exits: Vec<C>,
+}
+
+impl<C: Count> Sweep<C> {
+    pub fn timelines(&self) -> Result<C> {
+        total(&self.exits)
+    }
+}
+
+pub fn total<C: Count>(counts: &[C]) -> Result<C> {
+    counts.iter().try_fold(C::zero(), |total, count| {
+        total
+            .checked_add(count)
+            .ok_or_else(|| anyhow!("total overflows a {}", C::NAME))
+    })
+}
+
+/// A grid `width` columns wide with the start in the middle of the first row and splitters on
+/// every other row after it, placed by a xorshift generator seeded with `seed`.
+pub fn synthetic(width: usize, height: usize, seed: u64) -> Grid {
+    let mut state = seed.max(1);
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let values = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    if y == 0 {
+                        if x == width / 2 {
+                            Value::Start
+                        } else {
+                            Value::Empty
+                        }
+                    } else if y % 2 == 0 && random() % 4 == 0 {
+                        Value::Splitter
+                    } else {
+                        Value::Empty
+                    }
+                })
+                .collect()
+        })
+        .collect::<Vec<_>>();
+
+    Grid::from(values)
+}
+
+/// The splitters of a grid and where the beams leaving each of them end up. Beams only travel
+/// down, so every edge points to a later row and the graph is acyclic.
+#[derive(Debug)]
+pub struct SplitterGraph {
+    /// Ordered by row, then column, which is a topological order.
+    pub splitters: Vec<Splitter>,
+    /// Where the beam from each source goes.
+    pub starts: Vec<Target>,
+    pub width: usize,
+}
+
+#[derive(Debug)]
+pub struct Splitter {
+    pub position: (usize, usize),
+    /// The targets of the left and right beams. A beam that would leave the grid sideways is
+    /// dropped.
+    pub targets: Vec<Target>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    Splitter(usize),
+    /// The beam leaves the bottom of the grid in this column.
+    Exit(usize),
+}
+
+impl SplitterGraph {
+    /// Counts the paths from the start into every splitter and out of every exit column.
+    pub fn paths<C: Count>(&self) -> Result<Paths<C>> {
+        let mut paths = Paths {
+            incoming: vec![C::zero(); self.splitters.len()],
+            exits: vec![C::zero(); self.width],
+        };
+        for start in &self.starts {
+            paths.add(*start, &C::one())?;
+        }
+        for (i, splitter) in self.splitters.iter().enumerate() {
+            let count = paths.incoming[i].clone();
+            if count.is_zero() {
+                continue;
+            }
+            for target in &splitter.targets {
+                paths.add(*target, &count)?;
+            }
+        }
+
+        Ok(paths)
+    }
+
+    pub fn report(&self) -> Result<String> {
+        let paths = self.paths::<u128>()?;
+        let never_hit = paths
+            .never_hit()
+            .map(|i| format!("{:?}", self.splitters[i].position))
+            .collect::<Vec<_>>();
+        let exits = paths
+            .exits
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| !count.is_zero())
+            .map(|(column, count)| format!("{column}: {count}"))
+            .collect::<Vec<_>>();
+
+        Ok(format!(
+            "splitters: {}, hit: {}\nnever hit: {}\npaths per exit column: {}\ntimelines: {}",
+            self.splitters.len(),
+            paths.reachable().count(),
+            never_hit.join(", "),
+            exits.join(", "),
+            total(&paths.exits)?
+        ))
+    }
+}
+
+impl TryFrom<&Grid> for SplitterGraph {
+    type Error = anyhow::Error;
+
+    /// Fails on grids with mirrors or walls, whose beams can leave the graph's downward order.
+    fn try_from(grid: &Grid) -> Result<SplitterGraph> {
+        if !grid.is_downward() {
+            bail!("the splitter graph needs a grid without mirrors or walls");
+        }
+        let (width, height) = grid.size;
+        let positions = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|(x, y)| grid.values[*y][*x] == Value::Splitter)
+            .collect::<Vec<_>>();
+        // the rows of the splitters in every column, with their index
+        let mut columns = vec![vec![]; width];
+        for (i, (x, y)) in positions.iter().enumerate() {
+            columns[*x].push((*y, i));
+        }
+        let below = |(x, y): (usize, usize)| {
+            let column = &columns[x];
+            let next = column.partition_point(|(row, _)| *row <= y);
+            column
+                .get(next)
+                .map_or(Target::Exit(x), |(_, i)| Target::Splitter(*i))
+        };
+
+        let splitters = positions
+            .iter()
+            .map(|&(x, y)| Splitter {
+                position: (x, y),
+                targets: [x.checked_sub(1), Some(x + 1).filter(|x| *x < width)]
+                    .into_iter()
+                    .flatten()
+                    .map(|x| below((x, y)))
+                    .collect(),
+            })
+            .collect();
+
+        Ok(SplitterGraph {
+            splitters,
+            starts: grid.sources.iter().map(|source| below(*source)).collect(),
+            width,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Paths<C> {
+    /// The number of distinct paths from the start reaching each splitter.
+    pub incoming: Vec<C>,
+    /// The number of distinct paths leaving the bottom of the grid in each column.
+    pub exits: Vec<C>,
+}
+
+impl<C: Count> Paths<C> {
+    fn add(&mut self, target: Target, count: &C) -> Result<()> {
+        let slot = match target {
+            Target::Splitter(i) => &mut self.incoming[i],
+            Target::Exit(column) => &mut self.exits[column],
+        };
+        *slot = slot
+            .checked_add(count)
+            .ok_or_else(|| anyhow!("path count overflows a {}", C::NAME))?;
+
+        Ok(())
+    }
+
+    pub fn reachable(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.incoming.len()).filter(|i| !self.incoming[*i].is_zero())
+    }
+
+    pub fn never_hit(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.incoming.len()).filter(|i| self.incoming[*i].is_zero())
+    }
+}
+
+/// Counts the splitters hit when beams can turn in any direction, following every beam once.
+pub fn trace(grid: &Grid) -> usize {
+    let mut seen = vec![false; grid.size.0 * grid.size.1 * 4];
+    let mut hit = vec![false; grid.size.0 * grid.size.1];
+    let mut queue = grid.sources().collect::<Vec<_>>();
+    while let Some(beam) = queue.pop() {
+        let index = grid.state_index(beam);
+        if seen[index] {
+            continue;
+        }
+        seen[index] = true;
+
+        if let Advance::Enters { cell, beams } = grid.advance(beam) {
+            if grid.values[cell.1][cell.0] == Value::Splitter {
+                hit[cell.1 * grid.size.0 + cell.0] = true;
+            }
+            queue.extend(beams.into_iter().flatten());
+        }
+    }
+
+    hit.iter().filter(|hit| **hit).count()
+}
+
+/// Counts the distinct paths from the sources until a beam leaves the grid or hits a wall, failing
+/// if a beam can loop forever.
+pub fn timelines(grid: &Grid) -> Result<u128> {
+    #[derive(Clone, Copy)]
+    enum State {
+        New,
+        Open,
+        Done(u128),
+    }
+
+    let mut states = vec![State::New; grid.size.0 * grid.size.1 * 4];
+    let mut total = 0u128;
+    for source in grid.sources() {
+        // depth-first, summing the paths of a beam once all beams it turns into are done
+        let mut stack = vec![source];
+        while let Some(&beam) = stack.last() {
+            let index = grid.state_index(beam);
+            let (ends, beams) = match grid.advance(beam) {
+                Advance::Enters { beams, .. } => (false, beams),
+                Advance::Ends => (true, [None, None]),
+            };
+            match states[index] {
+                State::Done(_) => {
+                    stack.pop();
+                }
+                State::New => {
+                    states[index] = State::Open;
+                    for next in beams.into_iter().flatten() {
+                        match states[grid.state_index(next)] {
+                            State::New => stack.push(next),
+                            State::Open => bail!(
+                                "beam loops forever through ({}, {}) heading {:?}",
+                                next.position.0,
+                                next.position.1,
+                                next.direction
+                            ),
+                            State::Done(_) => {}
+                        }
+                    }
+                }
+                State::Open => {
+                    let count = if ends {
+                        1
+                    } else {
+                        beams
+                            .into_iter()
+                            .flatten()
+                            .try_fold(0u128, |sum, next| match states[grid.state_index(next)] {
+                                State::Done(count) => sum.checked_add(count),
+                                _ => unreachable!(
+                                    "beams are finished before the beams they come from"
+                                ),
+                            })
+                            .ok_or_else(|| anyhow!("timelines overflow a u128"))?
+                    };
+                    states[index] = State::Done(count);
+                    stack.pop();
+                }
+            }
+        }
+        if let State::Done(count) = states[grid.state_index(source)] {
+            total = total
+                .checked_add(count)
+                .ok_or_else(|| anyhow!("timelines overflow a u128"))?;
+        }
+    }
+
+    Ok(total)
+}
+
+/// A beam that has just left `position`, travelling in `direction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Beam {
+    pub position: (usize, usize),
+    pub direction: Direction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// The two directions at right angles to this one.
+    fn sides(self) -> [Direction; 2] {
+        match self {
+            Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
+            Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Advance {
+    /// The beam left the grid or was absorbed.
+    Ends,
+    /// The beam entered `cell` and carries on as `beams`. Splitters drop a beam that would start
+    /// outside the grid.
+    Enters {
+        cell: (usize, usize),
+        beams: [Option<Beam>; 2],
+    },
+}
+
+#[derive(Debug)]
+pub struct Grid {
+    pub values: Vec<Vec<Value>>,
+    pub size: (usize, usize),
+    /// Ordered by row, then column.
+    pub sources: Vec<(usize, usize)>,
+}
+
+impl Grid {
+    /// Whether every beam keeps travelling down, which is what the sweep and the splitter graph
+    /// expect.
+    pub fn is_downward(&self) -> bool {
+        self.values
+            .iter()
+            .flatten()
+            .all(|value| matches!(value, Value::Empty | Value::Start | Value::Splitter))
+    }
+
+    pub fn sources(&self) -> impl Iterator<Item = Beam> + '_ {
+        self.sources.iter().map(|position| Beam {
+            position: *position,
+            direction: Direction::Down,
+        })
+    }
+
+    fn step(&self, (x, y): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        let (x, y) = match direction {
+            Direction::Up => (x, y.checked_sub(1)?),
+            Direction::Down => (x, y + 1),
+            Direction::Left => (x.checked_sub(1)?, y),
+            Direction::Right => (x + 1, y),
+        };
+
+        (x < self.size.0 && y < self.size.1).then_some((x, y))
+    }
+
+    fn advance(&self, beam: Beam) -> Advance {
+        let Some(cell) = self.step(beam.position, beam.direction) else {
+            return Advance::Ends;
+        };
+        let turn = |direction| {
+            [
+                Some(Beam {
+                    position: cell,
+                    direction,
+                }),
+                None,
+            ]
+        };
+        let beams = match (self.values[cell.1][cell.0], beam.direction) {
+            (Value::Empty | Value::Start, direction) => turn(direction),
+            (Value::Splitter, direction) => direction.sides().map(|side| {
+                self.step(cell, side).map(|position| Beam {
+                    position,
+                    direction,
+                })
+            }),
+            (Value::Wall, _) => return Advance::Ends,
+            (Value::Mirror, Direction::Up) | (Value::BackMirror, Direction::Down) => {
+                turn(Direction::Right)
+            }
+            (Value::Mirror, Direction::Down) | (Value::BackMirror, Direction::Up) => {
+                turn(Direction::Left)
+            }
+            (Value::Mirror, Direction::Left) | (Value::BackMirror, Direction::Right) => {
+                turn(Direction::Down)
+            }
+            (Value::Mirror, Direction::Right) | (Value::BackMirror, Direction::Left) => {
+                turn(Direction::Up)
+            }
+        };
+
+        Advance::Enters { cell, beams }
+    }
+
+    fn state_index(&self, beam: Beam) -> usize {
+        let (x, y) = beam.position;
+        (y * self.size.0 + x) * 4 + beam.direction as usize
+    }
+}
+
+impl From<Vec<Vec<Value>>> for Grid {
+    fn from(values: Vec<Vec<Value>>) -> Grid {
+        let size = (values[0].len(), values.len());
+        let sources = values
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, value)| **value == Value::Start)
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+
+        Grid {
+            values,
+            size,
+            sources,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Value {
+    Empty,
+    /// Sends a beam down.
+    Start,
+    /// Replaces a beam with two beams starting on either side of it, in the same direction.
+    Splitter,
+    /// `/`
+    Mirror,
+    /// `\`
+    BackMirror,
+    /// Absorbs beams.
+    Wall,
+}
+
+pub fn parse(input: &str) -> Result<Grid> {
+    let input = normalize(input);
+    let grid = finish_parse(&input, parse_input(&input))?;
+
+    Ok(grid)
+}
+
+fn parse_input(input: &str) -> IResult<&str, Grid> {
+    map(parse_lines, |values| values.into()).parse(input)
+}
+
+fn parse_lines(input: &str) -> IResult<&str, Vec<Vec<Value>>> {
+    char_grid(alt((
+        parse_empty,
+        parse_start,
+        parse_splitter,
+        parse_mirror,
+        parse_wall,
+    )))
+    .parse(input)
+}
+
+fn parse_empty(input: &str) -> IResult<&str, Value> {
+    value(Value::Empty, tag(".")).parse(input)
+}
+
+fn parse_start(input: &str) -> IResult<&str, Value> {
+    value(Value::Start, tag("S")).parse(input)
+}
+
+fn parse_splitter(input: &str) -> IResult<&str, Value> {
+    value(Value::Splitter, tag("^")).parse(input)
+}
+
+fn parse_mirror(input: &str) -> IResult<&str, Value> {
+    alt((
+        value(Value::Mirror, tag("/")),
+        value(Value::BackMirror, tag("\\")),
+    ))
+    .parse(input)
+}
+
+fn parse_wall(input: &str) -> IResult<&str, Value> {
+    value(Value::Wall, tag("#")).parse(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_lib::ParseError;
+
+    const TESTDATA: &str = include_str!("test.txt");
+
+    #[test]
+    fn test_parse_one_testdata() -> Result<()> {
+        let grid = parse(TESTDATA)?;
+
+        let res = part_one(&grid)?;
+        assert_eq!(res, 21);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two_testdata() -> Result<()> {
+        let grid = parse(TESTDATA)?;
+
+        let res = part_two(&grid)?;
+        assert_eq!(res, 40);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf_testdata() -> Result<()> {
+        let input = TESTDATA.replace('\n', " \r\n");
+        let grid = parse(&input)?;
+        assert_eq!(part_one(&grid)?, 21);
+        assert_eq!(part_two(&grid)?, 40);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_graph_testdata() -> Result<()> {
+        let graph = SplitterGraph::try_from(&parse(TESTDATA)?)?;
+
+        assert_eq!(graph.starts, vec![Target::Splitter(0)]);
+        assert_eq!(graph.splitters[0].position, (7, 2));
+        assert_eq!(
+            graph.splitters[0].targets,
+            vec![Target::Splitter(1), Target::Splitter(2)]
+        );
+        assert_eq!(graph.splitters.len(), 22);
+
+        let paths = graph.paths::<u64>()?;
+        let never_hit = paths
+            .never_hit()
+            .map(|i| graph.splitters[i].position)
+            .collect::<Vec<_>>();
+        assert_eq!(never_hit, vec![(9, 14)]);
+        assert_eq!(&paths.incoming[..8], &[1, 1, 1, 1, 2, 1, 1, 3]);
+        assert_eq!(
+            paths.exits,
+            vec![1, 0, 2, 0, 10, 0, 11, 0, 11, 0, 2, 1, 1, 0, 1]
+        );
+        assert_eq!(total(&paths.exits)?, 40);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sweep_matches_graph() -> Result<()> {
+        for seed in 1..=20 {
+            let grid = synthetic(60 + seed as usize, 50, seed);
+            let graph = SplitterGraph::try_from(&grid)?;
+
+            let hit = graph.paths::<bool>()?.reachable().count();
+            assert_eq!(sweep::<bool>(&grid)?.splits, hit, "seed {seed}");
+            let paths = graph.paths::<u128>()?;
+            let sweep = sweep::<u128>(&grid)?;
+            assert_eq!(sweep.exits, paths.exits, "seed {seed}");
+            assert_eq!(sweep.splits, hit);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sweep_overflow() {
+        let grid = synthetic(400, 600, 3);
+        assert!(sweep::<bool>(&grid).is_ok());
+        let err = sweep::<u64>(&grid).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("timelines overflow a u64 in row "));
+    }
+
+    #[test]
+    fn test_trace_matches_sweep() -> Result<()> {
+        for seed in 1..=10 {
+            let grid = synthetic(40, 30, seed);
+            assert_eq!(trace(&grid), sweep::<bool>(&grid)?.splits, "seed {seed}");
+            assert_eq!(
+                timelines(&grid)?,
+                sweep::<u128>(&grid)?.timelines()?,
+                "seed {seed}"
+            );
+        }
+
+        let grid = parse("..S..S.\n.......\n..^.S^.\n.......\n.^.^...\n")?;
+        assert_eq!(grid.sources.len(), 3);
+        assert_eq!(trace(&grid), sweep::<bool>(&grid)?.splits);
+        assert_eq!(timelines(&grid)?, sweep::<u128>(&grid)?.timelines()?);
+        assert_eq!(
+            SplitterGraph::try_from(&grid)?.paths::<u128>()?.exits,
+            sweep::<u128>(&grid)?.exits
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mirrors_and_walls() -> Result<()> {
+        let grid = parse(".S...\n.....\n.^...\n#....\n../..\n")?;
+        assert!(!grid.is_downward());
+        assert!(SplitterGraph::try_from(&grid).is_err());
+        assert_eq!(part_one(&grid)?, 1);
+        // one beam is absorbed by the wall, the other leaves through the left edge
+        assert_eq!(part_two(&grid)?, 2);
+
+        let grid = parse(".S.\n.^.\n./.\n.\\.\n")?;
+        assert_eq!(part_one(&grid)?, 1);
+        assert_eq!(part_two(&grid)?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_loop() -> Result<()> {
+        let grid = parse(".....\n./.\\.\n.S...\n.\\./.\n")?;
+        assert_eq!(part_one(&grid)?, 0);
+        let err = part_two(&grid).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "beam loops forever through (1, 2) heading Down"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ragged_grid() -> Result<()> {
+        for (input, line, column) in [("..S..\n..\n..^..\n", 2, 3), ("..S\n...^.\n.....\n", 2, 4)] {
+            let err = parse(input).unwrap_err().downcast::<ParseError>()?;
+            assert_eq!((err.line, err.column), (line, column), "{input:?}");
+            assert_eq!(err.message, "row is not as wide as the first row");
+        }
+
+        Ok(())
+    }
+}